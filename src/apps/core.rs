@@ -1,27 +1,67 @@
+mod channels;
 mod event_handlers;
+mod hotspot;
+mod interface;
+mod layout;
+mod monitor;
+mod mouse;
+mod navigation;
+mod notifications;
+mod profile_name;
+mod prune;
+mod radio;
 mod run;
 mod saved_connection;
+mod signal;
+#[cfg(test)]
+mod snapshot_tests;
+mod vpn_connection;
 mod widget;
 use crate::AppState;
 use crate::WifiNetwork;
+use crate::apps::core::hotspot::Hotspot;
+use crate::apps::core::interface::Interfaces;
+use crate::apps::core::monitor::Monitor;
+use crate::apps::core::notifications::Notifications;
+use crate::apps::core::prune::DEFAULT_PRUNE_DAYS;
+use crate::apps::core::radio::RadioState;
 use crate::apps::core::saved_connection::SavedConnections;
+use crate::apps::core::vpn_connection::VpnConnections;
 use crate::apps::handlers::WifiInputState;
 use crate::apps::handlers::flags::Flags;
+use crate::apps::handlers::mouse::Mouse;
+use crate::apps::handlers::status::Status;
+use crate::error::Error;
+use crate::utils::cli::Args;
 use crate::utils::connect::connect_to_saved_network;
+use crate::utils::connection_info::{ConnectionInfo, Connectivity, refresh_connection_info};
+use crate::utils::delete_connection::SavedProfile;
+use crate::utils::diagnostics::Diagnostics;
 use crate::utils::disconnect_connection::disconnect_connected_network;
+use crate::utils::failure::FailureKind;
+use crate::utils::portal::{PortalDetected, detect_portal, open_portal};
 use crate::utils::scan::scan_networks;
+use crate::utils::signal_history::SignalHistory;
 use crossterm::cursor;
 use crossterm::cursor::DisableBlinking;
 use crossterm::cursor::EnableBlinking;
 use crossterm::execute;
 use ratatui::Frame;
 use ratatui::layout::Position;
+use ratatui::widgets::TableState;
+use std::cell::{Cell, RefCell};
 use std::io;
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
 use std::sync::Arc;
 mod delete_handler;
+mod diagnostics;
+mod errors;
 mod help_handlers;
-use std::sync::RwLock;
 use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{PoisonError, RwLock};
+use std::time::Instant;
 
 #[derive(Debug)]
 pub struct App {
@@ -29,15 +69,52 @@ pub struct App {
     wifi_list: Arc<RwLock<Vec<WifiNetwork>>>,
     selected: usize,
     app_state: AppState,
+    // view that received input last, kept to log the transitions between views
+    view: &'static str,
     saved_connection: SavedConnections,
+    vpn_connection: VpnConnections,
+    hotspot: Hotspot,
+    interfaces: Interfaces,
+    radio: RadioState,
+    monitor: Monitor,
+    // results of actions and changes made outside of nmtui, shown as toasts and in the history
+    notifications: Notifications,
+    // the profile deleted last with its secrets and when, so that the deletion can be undone
+    deleted_profile: Option<(SavedProfile, Instant)>,
+    // signal readings of every access point seen by the scans, for the signal panel
+    signal_history: Arc<RwLock<SignalHistory>>,
+    last_signal_sample: Option<Instant>,
+    // current connection and connectivity shown in the status bar, refreshed in the background
+    connection_info: Arc<RwLock<ConnectionInfo>>,
+    // wireless interface every nmcli call is pinned to, None lets NetworkManager pick
+    ifname: Option<String>,
+    // command the sign-in page of a captive portal is opened with
+    portal_browser: Option<String>,
+    // page opened to get to the sign-in page, the connectivity check uri of NetworkManager if None
+    portal_url: Option<String>,
+    // networks found behind a captive portal by the check run after connecting
+    portals: Sender<PortalDetected>,
+    portal_receiver: Receiver<PortalDetected>,
+    // results of the connectivity checks of the diagnostics view, filled in by a background thread
+    diagnostics: Arc<RwLock<Diagnostics>>,
+    // network namespace the diagnostics run in
+    netns: Option<String>,
+    // saved networks unused for more days than this are picked by the prune preview
+    prune_days: u64,
+    // failures of the work done in background threads, shown in the error popup
+    errors: Sender<Error>,
+    error_receiver: Receiver<Error>,
+    // where the last frame drew the rows and buttons that can be clicked
+    mouse: Mouse,
+    // scroll position of the network list, updated while rendering so it is kept between frames
+    network_table_state: RefCell<TableState>,
+    // where the input popup drawn by the last frame wants the terminal cursor, if one is open
+    cursor: Cell<Option<Position>>,
     flags: Flags,
 }
 
 impl Default for App {
-    /// Constructs a new `App` with default field values and begins an initial Wi‑Fi scan.
-    ///
-    /// All fields are initialized using their `Default` implementations. The `wifi_list` is created
-    /// as an empty, shared, thread-safe vector and an initial network scan is triggered to populate it.
+    /// Constructs a new `App` without any command line options, see [`App::new`].
     ///
     /// # Examples
     ///
@@ -47,17 +124,76 @@ impl Default for App {
     /// assert!(app.wifi_list.lock().unwrap().is_empty());
     /// ```
     fn default() -> Self {
+        Self::new(Args::default())
+    }
+}
+
+impl App {
+    /// Constructs a new `App` from the command line options and begins an initial Wi‑Fi scan.
+    ///
+    /// All other fields are initialized using their `Default` implementations. The `wifi_list` is created
+    /// as an empty, shared, thread-safe vector and an initial network scan is triggered to populate it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let app = App::new(Args {
+    ///     ifname: Some("wlan0".to_string()),
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(app.ifname.as_deref(), Some("wlan0"));
+    /// ```
+    pub fn new(args: Args) -> Self {
         let wifi_list = Arc::new(RwLock::new(Vec::new()));
 
         // Setting up scanning flag
         let is_scanning = Arc::new(AtomicBool::new(true));
-        scan_networks(wifi_list.clone(), is_scanning.clone());
+        let signal_history = Arc::new(RwLock::new(SignalHistory::default()));
+        let (errors, error_receiver) = mpsc::channel();
+        let (portals, portal_receiver) = mpsc::channel();
+        scan_networks(
+            wifi_list.clone(),
+            is_scanning.clone(),
+            signal_history.clone(),
+            args.ifname.clone(),
+            errors.clone(),
+        );
+
+        let connection_info = Arc::new(RwLock::new(ConnectionInfo::default()));
+        refresh_connection_info(connection_info.clone(), args.ifname.clone());
+
+        let mut radio = RadioState::default();
+        radio.refresh();
         Self {
             wifi_credentials: WifiInputState::default(),
             wifi_list,
             selected: 0,
             app_state: AppState::default(),
+            view: "networks",
             saved_connection: SavedConnections::default(),
+            vpn_connection: VpnConnections::default(),
+            hotspot: Hotspot::default(),
+            interfaces: Interfaces::default(),
+            radio,
+            monitor: Monitor::start(),
+            notifications: Notifications::default(),
+            deleted_profile: None,
+            signal_history,
+            last_signal_sample: None,
+            connection_info,
+            ifname: args.ifname,
+            portal_browser: args.portal_browser,
+            portal_url: args.portal_url,
+            portals,
+            portal_receiver,
+            diagnostics: Arc::default(),
+            netns: args.netns,
+            prune_days: args.prune_after.unwrap_or(DEFAULT_PRUNE_DAYS),
+            errors,
+            error_receiver,
+            mouse: Mouse::default(),
+            network_table_state: RefCell::default(),
+            cursor: Cell::default(),
             flags: {
                 Flags {
                     is_scanning,
@@ -66,13 +202,12 @@ impl Default for App {
             },
         }
     }
-}
 
-impl App {
-    /// Render the application UI and manage terminal cursor visibility and position for SSID/password popups.
+    /// Render the application UI and manage terminal cursor visibility and position for the input popups.
     ///
-    /// When an SSID or password popup is visible, this sets the terminal cursor to the popup's input position
-    /// and enables cursor blinking; otherwise it hides the cursor and disables blinking.
+    /// When an SSID, password or VPN input popup is visible, this puts the terminal cursor where the
+    /// popup was actually drawn and enables cursor blinking; otherwise it hides the cursor and
+    /// disables blinking.
     ///
     /// # Examples
     ///
@@ -83,14 +218,9 @@ impl App {
     fn draw(&self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
 
-        // Set cursor position for SSID or Password popups
-        if self.wifi_credentials.flags.show_ssid_popup
-            || self.wifi_credentials.flags.show_password_popup
-        {
-            frame.set_cursor_position(Position::new(
-                frame.area().x + frame.area().width / 4 + self.wifi_credentials.cursor_pos + 1,
-                frame.area().y + frame.area().height / 4 + 1,
-            ));
+        // the input popups record their cursor while rendering, so it follows their size and scroll
+        if let Some(position) = self.cursor.get() {
+            frame.set_cursor_position(position);
             // The reason for using io::stdout() here is that the crossterm execute! macro needs a writable output target to send
             // terminal commands to. io::stdout() provides a handle to the standard output (the terminal), so the commands (Show, MoveTo, EnableBlinking)
             // are sent to the terminal for immediate effect.
@@ -118,53 +248,108 @@ impl App {
     }
 
     fn prepare_to_connect(&mut self) {
-        match self.wifi_list.write() {
-            Ok(wifi_list) => {
-                // if the selected network is already in use, do nothing
-                if wifi_list[self.selected].in_use {
-                }
-                // if the network is unsecured, connect directly so logic will be similar to saved network
-                else if wifi_list[self.selected].security == "Unsecured" {
-                    let status = connect_to_saved_network(&wifi_list[self.selected].ssid);
-                    self.wifi_credentials.status = status;
-                    self.wifi_credentials.flags.show_status_popup = true;
-
-                    // refresh the network list after connection attempt
-                    scan_networks(self.wifi_list.clone(), self.flags.is_scanning.clone());
-                }
-                // if the selected network is hidden network option
-                // the show status popup will be handled by the password input listener
-                else if wifi_list[self.selected].ssid == "Connect to Hidden network" {
-                    self.wifi_credentials.flags.is_hidden = true;
-                    self.wifi_credentials.flags.show_ssid_popup = true;
-                    // if the wifi is hidden, then the ssid should be entered manually and the
-                    // passoword popupo should be shown by the listner of the enter of the in the
-                    // ssid input
-                    self.wifi_credentials.flags.show_password_popup = false;
-
-                    // clear previous ssid and password
-                    self.wifi_credentials.ssid.clear();
-                    self.wifi_credentials.password.clear();
-                }
-                // if the network is saved, connect directly
-                else if wifi_list[self.selected].is_saved {
-                    let status = connect_to_saved_network(&wifi_list[self.selected].ssid);
-                    self.wifi_credentials.status = status;
-                    self.wifi_credentials.flags.show_status_popup = true;
-                    // refresh the network list after connection attempt
-                    scan_networks(self.wifi_list.clone(), self.flags.is_scanning.clone());
-                }
-                // else show the password popup
-                else {
-                    self.wifi_credentials.flags.show_password_popup = true;
-                    self.wifi_credentials.ssid = wifi_list[self.selected].ssid.clone();
-                    self.wifi_credentials.password.clear();
-                    self.wifi_credentials.reset_cursor_position();
-                }
-            }
-            Err(_) => {
-                panic!("Failed to acquire lock on wifi_list");
-            }
+        // nothing can be connected while the radio is off, the list only explains how to turn it on
+        if !self.radio.wifi_enabled || !self.radio.networking_enabled {
+            return;
+        }
+        // a password entered from here is for the network, not for a profile of the saved list
+        self.wifi_credentials.profile = None;
+        // the list is replaced as a whole by the scans, so it is still usable after a panic elsewhere
+        let wifi_list = self
+            .wifi_list
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        // nothing to connect to before the first scan finished
+        if self.selected >= wifi_list.len() {
+            return;
+        }
+        // if the selected network is already in use, do nothing
+        if wifi_list[self.selected].in_use {
+        }
+        // if the network is unsecured, connect directly so logic will be similar to saved network
+        else if wifi_list[self.selected].security == "Unsecured" {
+            let status =
+                connect_to_saved_network(&wifi_list[self.selected].ssid, self.ifname.as_deref());
+            drop(wifi_list);
+            self.report_connection(status);
+
+            // refresh the network list after connection attempt
+            self.scan();
+        }
+        // if the selected network is hidden network option
+        // the show status popup will be handled by the password input listener
+        else if wifi_list[self.selected].ssid == "Connect to Hidden network" {
+            self.wifi_credentials.flags.is_hidden = true;
+            self.wifi_credentials.flags.show_ssid_popup = true;
+            // if the wifi is hidden, then the ssid should be entered manually and the
+            // passoword popupo should be shown by the listner of the enter of the in the
+            // ssid input
+            self.wifi_credentials.flags.show_password_popup = false;
+
+            // clear previous ssid and password
+            self.wifi_credentials.ssid.clear();
+            self.wifi_credentials.password.clear();
+        }
+        // if the network is saved, connect directly
+        else if wifi_list[self.selected].is_saved {
+            // remembered so that the password can be entered again if the saved one is rejected
+            self.wifi_credentials.ssid = wifi_list[self.selected].ssid.clone();
+            let status =
+                connect_to_saved_network(&wifi_list[self.selected].ssid, self.ifname.as_deref());
+            drop(wifi_list);
+            self.report_connection(status);
+            // refresh the network list after connection attempt
+            self.scan();
+        }
+        // else show the password popup
+        else {
+            self.wifi_credentials.flags.show_password_popup = true;
+            self.wifi_credentials.ssid = wifi_list[self.selected].ssid.clone();
+            self.wifi_credentials.password.clear();
+            self.wifi_credentials.reset_cursor_position();
+        }
+    }
+
+    /// Report the result of connecting to a network.
+    ///
+    /// After a successful connection NetworkManager is asked to probe the internet in the
+    /// background, see [`App::process_portal_checks`] for a network found to be behind a portal.
+    fn report_connection(&mut self, status: Status) {
+        if status.status_code.success() {
+            detect_portal(
+                status.status_message.clone(),
+                self.portal_url.clone(),
+                self.portals.clone(),
+            );
+        }
+        self.report(status);
+    }
+
+    /// Report the networks found to hold the traffic back behind a captive portal since the last
+    /// frame, in the status popup instead of as a success.
+    ///
+    /// The sign-in page is opened when a browser was configured with `--portal-browser`.
+    pub fn process_portal_checks(&mut self) {
+        let portals: Vec<PortalDetected> = self.portal_receiver.try_iter().collect();
+        for portal in portals {
+            self.connection_info
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .connectivity = Connectivity::Portal;
+            let hint = match (&portal.url, &self.portal_browser) {
+                (Some(url), Some(browser)) => match open_portal(browser, url) {
+                    Ok(()) => format!("Opened {} with `{}`.", url, browser),
+                    Err(e) => format!("`{}` could not be started: {}", browser, e),
+                },
+                (Some(url), None) => format!("Open {} to get to the sign-in page.", url),
+                (None, _) => "Open any http:// page to get to the sign-in page.".to_string(),
+            };
+            let mut status = Status::new(
+                format!("{}\n{}", portal.message, hint),
+                ExitStatus::from_raw(0),
+            );
+            status.failure = Some(FailureKind::CaptivePortal);
+            self.report(status);
         }
     }
 
@@ -180,12 +365,25 @@ impl App {
     }
 
     fn disconnect(&mut self) {
-        self.wifi_credentials.status = disconnect_connected_network(self.wifi_list.clone());
-        self.wifi_credentials.flags.show_status_popup = true;
-        scan_networks(self.wifi_list.clone(), self.flags.is_scanning.clone());
+        let status = disconnect_connected_network(self.wifi_list.clone(), self.ifname.as_deref());
+        self.report(status);
+        self.scan();
     }
 
     fn reset_selection(&mut self) {
         self.selected = 0;
     }
+
+    /// Start a background scan of the networks on the chosen interface, refreshing the status
+    /// bar along with it.
+    fn scan(&self) {
+        scan_networks(
+            self.wifi_list.clone(),
+            self.flags.is_scanning.clone(),
+            self.signal_history.clone(),
+            self.ifname.clone(),
+            self.errors.clone(),
+        );
+        refresh_connection_info(self.connection_info.clone(), self.ifname.clone());
+    }
 }
@@ -0,0 +1,217 @@
+use super::App;
+use crate::WifiNetwork;
+
+use crossterm::event::poll;
+use crossterm::event::{self, Event, KeyEvent};
+use std::collections::BTreeMap;
+use std::{io, time::Duration};
+
+// channels of the 2.4 GHz band that are always shown, so the free ones stand out as well
+const CHANNELS_2_4_GHZ: std::ops::RangeInclusive<u16> = 1..=13;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FrequencyBand {
+    Ghz2_4,
+    Ghz5,
+    Ghz6,
+}
+
+impl FrequencyBand {
+    fn from_frequency(frequency: u32) -> Option<Self> {
+        match frequency {
+            2400..=2500 => Some(FrequencyBand::Ghz2_4),
+            5150..=5895 => Some(FrequencyBand::Ghz5),
+            5925..=7125 => Some(FrequencyBand::Ghz6),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FrequencyBand::Ghz2_4 => "2.4 GHz",
+            FrequencyBand::Ghz5 => "5 GHz",
+            FrequencyBand::Ghz6 => "6 GHz",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChannelUsage {
+    pub channel: u16,
+    pub access_points: usize,
+    // strongest signal heard on the channel, a strong neighbour interferes more than a weak one
+    pub strongest_signal: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandUsage {
+    pub band: FrequencyBand,
+    pub channels: Vec<ChannelUsage>,
+}
+
+impl BandUsage {
+    pub fn access_points(&self) -> usize {
+        self.channels
+            .iter()
+            .map(|channel| channel.access_points)
+            .sum()
+    }
+}
+
+/// Group the scanned access points by band and channel.
+///
+/// Every 2.4 GHz channel is listed, even without access points, while the 5 and 6 GHz bands only
+/// list the occupied channels since they have too many to show. Bands without any access point
+/// other than 2.4 GHz are left out.
+pub fn channel_usage(networks: &[WifiNetwork]) -> Vec<BandUsage> {
+    let mut bands: BTreeMap<FrequencyBand, BTreeMap<u16, ChannelUsage>> = BTreeMap::new();
+
+    bands.insert(
+        FrequencyBand::Ghz2_4,
+        CHANNELS_2_4_GHZ
+            .map(|channel| {
+                (
+                    channel,
+                    ChannelUsage {
+                        channel,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect(),
+    );
+
+    for network in networks.iter().filter(|network| !network.bssid.is_empty()) {
+        let Some(band) = FrequencyBand::from_frequency(network.frequency) else {
+            continue;
+        };
+        let usage = bands
+            .entry(band)
+            .or_default()
+            .entry(network.channel)
+            .or_insert_with(|| ChannelUsage {
+                channel: network.channel,
+                ..Default::default()
+            });
+        usage.access_points += 1;
+        usage.strongest_signal = usage.strongest_signal.max(network.signal);
+    }
+
+    bands
+        .into_iter()
+        .map(|(band, channels)| BandUsage {
+            band,
+            channels: channels.into_values().collect(),
+        })
+        .collect()
+}
+
+impl App {
+    /// Handle keyboard input while the channel congestion view is active.
+    ///
+    /// - 'q' or Esc: close the view
+    /// - Ctrl+C: exit the application
+    /// - Ctrl+R: scan again
+    pub fn handle_channels(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('q'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_channels = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_channels = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('c'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.exit();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('r'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.scan();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access_point(channel: u16, frequency: u32, signal: u8) -> WifiNetwork {
+        WifiNetwork {
+            is_saved: false,
+            in_use: false,
+            ssid: format!("AP {}", channel),
+            security: "WPA2".to_string(),
+            bssid: format!("AA:BB:CC:DD:{:02X}:{:02X}", channel, signal),
+            signal,
+            channel,
+            frequency,
+        }
+    }
+
+    #[test]
+    fn counts_access_points_and_keeps_the_strongest_signal() {
+        let networks = [
+            access_point(6, 2437, 40),
+            access_point(6, 2437, 75),
+            access_point(36, 5180, 60),
+        ];
+        let bands = channel_usage(&networks);
+        assert_eq!(bands.len(), 2);
+
+        let band_2_4 = &bands[0];
+        assert_eq!(band_2_4.band, FrequencyBand::Ghz2_4);
+        assert_eq!(band_2_4.channels.len(), 13);
+        assert_eq!(
+            band_2_4.channels[5],
+            ChannelUsage {
+                channel: 6,
+                access_points: 2,
+                strongest_signal: 75,
+            }
+        );
+        assert_eq!(band_2_4.channels[0].access_points, 0);
+        assert_eq!(band_2_4.access_points(), 2);
+
+        // only the occupied 5 GHz channels are listed
+        assert_eq!(bands[1].band, FrequencyBand::Ghz5);
+        assert_eq!(bands[1].channels.len(), 1);
+        assert_eq!(bands[1].channels[0].channel, 36);
+    }
+
+    #[test]
+    fn skips_the_hidden_network_entry_and_unknown_frequencies() {
+        let mut hidden_entry = access_point(0, 0, 0);
+        hidden_entry.bssid.clear();
+        let networks = [
+            hidden_entry,
+            access_point(1, 900, 50),
+            access_point(1, 5955, 30),
+        ];
+        let bands = channel_usage(&networks);
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].access_points(), 0);
+        assert_eq!(bands[1].band, FrequencyBand::Ghz6);
+        assert_eq!(bands[1].access_points(), 1);
+    }
+}
@@ -1,11 +1,21 @@
 use super::App;
-use crate::utils::delete_connection::delete_connection;
-use crate::utils::scan::scan_networks;
+use super::notifications::ToastKind;
+use crate::apps::handlers::mouse::Button;
+use crate::apps::handlers::status::Status;
+use crate::utils::delete_connection::{
+    SavedProfile, UNDO_TIMEOUT, capture_profile, delete_connection, delete_connections,
+    restore_profile,
+};
+use crate::utils::failure::FailureKind;
+use crate::utils::saved_connection::network_profiles;
 
 use crossterm::event::KeyEventKind::Press;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, poll};
 use std::io;
-use std::time::Duration;
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
+use std::sync::PoisonError;
+use std::time::{Duration, Instant};
 
 impl App {
     /// Handle a pending delete-confirmation keyboard event when the confirmation overlay is shown.
@@ -86,7 +96,11 @@ impl App {
                 }) => {
                     self.flags.show_delete_confirmation = false;
                 }
-
+                Event::Mouse(mouse) => match self.mouse.clicked_button(&mouse) {
+                    Some(Button::Yes) => self.delete_connection(),
+                    Some(Button::No) => self.flags.show_delete_confirmation = false,
+                    _ => {}
+                },
                 _ => {}
             }
         };
@@ -95,8 +109,9 @@ impl App {
     /// Delete the currently selected connection and refresh the network list.
     ///
     /// If the app is showing saved connections, deletes the selected saved connection; otherwise deletes
-    /// the selected entry from the scanned Wi‑Fi list. After deletion, clears the delete-confirmation
-    /// flag and triggers a network rescan to refresh `wifi_list`.
+    /// the selected entry from the scanned Wi‑Fi list. The profile is read with its secrets first so that
+    /// it can be restored with [`App::undo_delete`] for a while, and nothing is deleted when that fails.
+    /// After deletion, clears the delete-confirmation flag and triggers a network rescan to refresh `wifi_list`.
     ///
     /// # Examples
     ///
@@ -107,27 +122,157 @@ impl App {
     /// app.delete_connection();
     /// ```
     pub fn delete_connection(&mut self) {
+        self.flags.show_delete_confirmation = false;
+        if self.flags.show_saved && !self.saved_connection.marked.is_empty() {
+            self.delete_marked_connections();
+            return;
+        }
         // Determine which connection to delete based on the current view
         //
         // here this one will delete from the saved connections list
+        let target = if self.flags.show_saved {
+            self.saved_connection
+                .connections
+                .get(self.saved_connection.selected_index)
+                .map(|connection| (connection.name.clone(), connection.uuid.clone()))
+        } else {
+            // this one will delete the connection from the wifi list
+            let ssid = self
+                .wifi_list
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(self.selected)
+                .map(|network| network.ssid.clone());
+            match ssid.map(|ssid| self.network_profile(&ssid)) {
+                Some(Ok(target)) => Some(target),
+                Some(Err(status)) => {
+                    self.report(status);
+                    None
+                }
+                None => None,
+            }
+        };
+        // the list may have been emptied by a refresh while the confirmation was open
+        let Some((name, uuid)) = target else {
+            return;
+        };
+
+        // a profile that cannot be restored is not deleted
+        let profile = match capture_profile(&name, &uuid) {
+            Ok(profile) => profile,
+            Err(e) => {
+                self.show_error(e);
+                return;
+            }
+        };
+        // the undo and the toast only once the profile is really gone, and the lists are refreshed
+        // after that so that they no longer show it
+        if let Err(e) = delete_connection(&uuid) {
+            self.show_error(e);
+            return;
+        }
+        self.deleted_profile = Some((profile, Instant::now()));
+        self.notifications.push(
+            ToastKind::Success,
+            format!("Deleted '{}', press (u) to undo", name.trim()),
+        );
+
         if self.flags.show_saved {
-            delete_connection(
-                self.saved_connection.connections[self.saved_connection.selected_index]
-                    .ssid
-                    .clone(),
-            );
             self.reset_saved_selection();
-            self.saved_connection.fetch_saved_connections();
+            self.refresh_saved_connections();
         } else {
-            // this one will delete the connection from the wifi list
-            delete_connection(
-                self.wifi_list.read().expect("WifiNetworks lock poisoned")[self.selected]
-                    .ssid
-                    .clone(),
-            );
             self.reset_selection();
-            scan_networks(self.wifi_list.clone(), self.flags.is_scanning.clone());
+            self.scan();
         }
-        self.flags.show_delete_confirmation = false;
+    }
+
+    /// Name and uuid of the only saved profile joining a network of the scan list.
+    ///
+    /// The profile is found by its ssid, it can be named anything. When several profiles join the
+    /// network it is not clear which one is meant, so they are left to be deleted one by one from
+    /// the saved list.
+    fn network_profile(&self, ssid: &str) -> Result<(String, String), Status> {
+        match network_profiles(ssid).map_err(Status::from)?.as_slice() {
+            [profile] => Ok(profile.clone()),
+            [] => Err(Status::new(
+                format!("No saved profile joins '{}'", ssid),
+                ExitStatus::from_raw(1 << 8),
+            )),
+            profiles => Err(Status::new(
+                format!(
+                    "{} saved profiles join '{}', delete them from the saved list (s)",
+                    profiles.len(),
+                    ssid
+                ),
+                ExitStatus::from_raw(1 << 8),
+            )),
+        }
+    }
+
+    /// Delete every connection marked in the saved list at once.
+    ///
+    /// A toast reports when all of them were deleted, otherwise the status popup lists the ones
+    /// that failed with the reason. Bulk deletes cannot be undone.
+    fn delete_marked_connections(&mut self) {
+        let profiles: Vec<(String, String)> = self
+            .saved_connection
+            .connections
+            .iter()
+            .filter(|connection| self.saved_connection.marked.contains(&connection.uuid))
+            .map(|connection| (connection.name.clone(), connection.uuid.clone()))
+            .collect();
+        match delete_connections(&profiles) {
+            Ok(summary) if summary.failed.is_empty() => {
+                self.notifications.push(
+                    ToastKind::Success,
+                    format!("Deleted {} connections", summary.deleted.len()),
+                );
+            }
+            Ok(summary) => {
+                let mut message = format!(
+                    "Deleted {} of {} connections, {} failed:",
+                    summary.deleted.len(),
+                    profiles.len(),
+                    summary.failed.len()
+                );
+                for (name, reason) in &summary.failed {
+                    message.push_str(&format!("\n{}: {}", name, reason));
+                }
+                let mut status = Status::new(message, ExitStatus::from_raw(1 << 8));
+                // the reasons are only listed, they are not one failure to explain
+                status.failure = Some(FailureKind::Other);
+                self.wifi_credentials.status = status;
+                self.wifi_credentials.flags.show_status_popup = true;
+            }
+            Err(e) => self.show_error(e),
+        }
+        self.saved_connection.marked.clear();
+        self.reset_saved_selection();
+        self.refresh_saved_connections();
+        self.scan();
+    }
+
+    /// The profile deleted last, while it can still be restored.
+    pub fn undoable_delete(&self) -> Option<&SavedProfile> {
+        self.deleted_profile
+            .as_ref()
+            .filter(|(_, deleted_at)| deleted_at.elapsed() < UNDO_TIMEOUT)
+            .map(|(profile, _)| profile)
+    }
+
+    /// Create the profile deleted last again, if it was deleted less than [`UNDO_TIMEOUT`] ago.
+    pub fn undo_delete(&mut self) {
+        let Some((profile, deleted_at)) = self.deleted_profile.take() else {
+            return;
+        };
+        if deleted_at.elapsed() >= UNDO_TIMEOUT {
+            return;
+        }
+        let status = restore_profile(&profile);
+        self.report(status);
+        if self.flags.show_saved {
+            self.refresh_saved_connections();
+        }
+        self.scan();
     }
 }
@@ -0,0 +1,75 @@
+use super::App;
+use crate::utils::diagnostics::run_diagnostics;
+
+use crossterm::event::poll;
+use crossterm::event::{self, Event, KeyEvent};
+use std::io;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+impl App {
+    /// Open the diagnostics view and start the checks on the chosen interface.
+    pub fn open_diagnostics(&mut self) {
+        self.run_diagnostics();
+        self.flags.show_diagnostics = true;
+    }
+
+    /// Run the checks again, unless they are still running.
+    fn run_diagnostics(&self) {
+        let running = self
+            .diagnostics
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .running;
+        if !running {
+            run_diagnostics(
+                self.diagnostics.clone(),
+                self.ifname.clone(),
+                self.netns.clone(),
+            );
+        }
+    }
+
+    /// Handle keyboard input while the diagnostics view is active.
+    ///
+    /// - 'q' or Esc: close the view
+    /// - Ctrl+C: exit the application
+    /// - 'r' or Ctrl+R: run the checks again
+    pub fn handle_diagnostics(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('q'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_diagnostics = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_diagnostics = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('c'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.exit();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('r'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.run_diagnostics();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use super::App;
+use crate::error::Error;
+use crate::utils::logger;
+
+impl App {
+    /// Show a failure of the backend in the error popup instead of aborting.
+    pub fn show_error(&mut self, error: Error) {
+        logger::error(&error.to_string());
+        self.wifi_credentials.status = error.into();
+        self.wifi_credentials.flags.show_status_popup = true;
+    }
+
+    /// Show the failures reported by background threads since the last frame.
+    ///
+    /// Only the last one stays on the screen, the earlier ones are still written to the log.
+    pub fn process_background_errors(&mut self) {
+        let errors: Vec<Error> = self.error_receiver.try_iter().collect();
+        for error in errors {
+            self.show_error(error);
+        }
+    }
+}
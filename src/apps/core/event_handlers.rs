@@ -1,9 +1,10 @@
 use super::App;
-use crate::utils::scan::scan_networks;
+use super::navigation::Jump;
 
 use crossterm::event::KeyEventKind::Press;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, poll};
 use std::io;
+use std::sync::PoisonError;
 use std::time::Duration;
 
 impl App {
@@ -65,7 +66,8 @@ impl App {
                     kind: Press,
                     ..
                 }) => {
-                    scan_networks(self.wifi_list.clone(), self.flags.is_scanning.clone());
+                    self.radio.refresh();
+                    self.scan();
                 }
                 Event::Key(KeyEvent {
                     code: KeyCode::Enter,
@@ -95,6 +97,48 @@ impl App {
                 }) => {
                     self.update_selected_network(1);
                 }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Home,
+                    kind: Press,
+                    ..
+                }) => {
+                    self.jump_network(Jump::First);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('g'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.jump_network(Jump::First);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::End,
+                    kind: Press,
+                    ..
+                }) => {
+                    self.jump_network(Jump::Last);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('G'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.jump_network(Jump::Last);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageUp,
+                    kind: Press,
+                    ..
+                }) => {
+                    self.jump_network(Jump::PageUp);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageDown,
+                    kind: Press,
+                    ..
+                }) => {
+                    self.jump_network(Jump::PageDown);
+                }
                 // vim style
                 Event::Key(KeyEvent {
                     code: KeyCode::Char('k'),
@@ -114,15 +158,14 @@ impl App {
                     code: KeyCode::Char('d'),
                     kind: Press,
                     ..
-                }) => {
-                    if self
-                        .wifi_list
-                        .read()
-                        .expect("Wifi list lock poisoned while deleting")[self.selected]
-                        .is_saved
-                    {
-                        self.flags.show_delete_confirmation = true;
-                    }
+                }) if self
+                    .wifi_list
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .get(self.selected)
+                    .is_some_and(|network| network.is_saved) =>
+                {
+                    self.flags.show_delete_confirmation = true;
                 }
                 Event::Key(KeyEvent {
                     code: KeyCode::Char('s'),
@@ -131,6 +174,76 @@ impl App {
                 }) => {
                     self.open_saved_list();
                 }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('a'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.open_hotspot();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('i'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.open_interfaces();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('v'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.open_vpn_list();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('w'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.toggle_wifi_radio();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('n'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.flags.show_networking_confirmation = true;
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.flags.show_channels = true;
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('u'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.undo_delete();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('D'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.open_diagnostics();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('m'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.flags.show_notifications = true;
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('t'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.toggle_signal_panel();
+                }
                 Event::Key(KeyEvent {
                     code: KeyCode::Char('x'),
                     kind: Press,
@@ -138,6 +251,9 @@ impl App {
                 }) => {
                     self.disconnect();
                 }
+                Event::Mouse(mouse) => {
+                    self.handle_network_mouse(mouse);
+                }
                 _ => {}
             };
         }
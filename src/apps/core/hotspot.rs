@@ -0,0 +1,203 @@
+use super::App;
+use crate::utils::hotspot::{
+    Band, generate_password, hotspot_details, is_hotspot_running, start_hotspot, stop_hotspot,
+};
+
+use crossterm::event::poll;
+use crossterm::event::{self, Event, KeyEvent};
+use std::{io, time::Duration};
+
+const DEFAULT_HOTSPOT_SSID: &str = "nmtui-hotspot";
+const GENERATED_PASSWORD_LENGTH: usize = 12;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum HotspotField {
+    #[default]
+    Ssid,
+    Band,
+    Password,
+}
+
+impl HotspotField {
+    fn next(&self) -> Self {
+        match self {
+            HotspotField::Ssid => HotspotField::Band,
+            HotspotField::Band => HotspotField::Password,
+            HotspotField::Password => HotspotField::Ssid,
+        }
+    }
+
+    fn previous(&self) -> Self {
+        match self {
+            HotspotField::Ssid => HotspotField::Password,
+            HotspotField::Band => HotspotField::Ssid,
+            HotspotField::Password => HotspotField::Band,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Hotspot {
+    pub ssid: String,
+    pub band: Band,
+    pub password: String,
+    // whether the hotspot profile is currently active
+    pub running: bool,
+    // field of the form that receives the typed characters while the hotspot is stopped
+    pub focused: HotspotField,
+}
+
+impl Hotspot {
+    // Refresh the running state and, when the hotspot is up, the ssid and password it uses
+    pub fn refresh(&mut self) {
+        self.running = is_hotspot_running();
+        if self.running
+            && let Some((ssid, password)) = hotspot_details()
+        {
+            self.ssid = ssid;
+            self.password = password;
+        }
+        if self.ssid.is_empty() {
+            self.ssid = DEFAULT_HOTSPOT_SSID.to_string();
+        }
+        if self.password.is_empty() {
+            self.password = generate_password(GENERATED_PASSWORD_LENGTH);
+        }
+    }
+
+    // WPA needs a password of 8 to 63 characters
+    pub fn is_valid(&self) -> bool {
+        let len = self.password.chars().count();
+        !self.ssid.is_empty() && (8..=63).contains(&len)
+    }
+}
+
+impl App {
+    /// Handle keyboard input while the hotspot view is active.
+    ///
+    /// While the hotspot is stopped the view is a form:
+    /// - Tab / Shift+Tab: move between the ssid, band and password fields
+    /// - typing / Backspace: edit the focused text field
+    /// - Left/Right: switch the band when the band field is focused
+    /// - Ctrl+G: generate a new password
+    /// - Enter: start the hotspot
+    ///
+    /// While it is running 'x' stops it. In both states 'q' or Esc closes the view and Ctrl+C
+    /// exits the application.
+    pub fn handle_hotspot(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            let hotspot = &mut self.hotspot;
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.close_hotspot();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('c'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.exit();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('q'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if hotspot.running => {
+                    self.close_hotspot();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('x'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if hotspot.running => {
+                    let status = stop_hotspot();
+                    self.report(status);
+                    self.hotspot.refresh();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Tab,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !hotspot.running => {
+                    hotspot.focused = hotspot.focused.next();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::BackTab,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !hotspot.running => {
+                    hotspot.focused = hotspot.focused.previous();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Left | event::KeyCode::Right,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !hotspot.running && hotspot.focused == HotspotField::Band => {
+                    hotspot.band = hotspot.band.toggle();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('g'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) if !hotspot.running => {
+                    hotspot.password = generate_password(GENERATED_PASSWORD_LENGTH);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char(c),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !hotspot.running => match hotspot.focused {
+                    HotspotField::Ssid => hotspot.ssid.push(c),
+                    HotspotField::Password => hotspot.password.push(c),
+                    HotspotField::Band => {}
+                },
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Backspace,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !hotspot.running => match hotspot.focused {
+                    HotspotField::Ssid => {
+                        hotspot.ssid.pop();
+                    }
+                    HotspotField::Password => {
+                        hotspot.password.pop();
+                    }
+                    HotspotField::Band => {}
+                },
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Enter,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !hotspot.running && hotspot.is_valid() => {
+                    let status = start_hotspot(
+                        &hotspot.ssid,
+                        hotspot.band,
+                        &hotspot.password,
+                        self.ifname.as_deref(),
+                    );
+                    self.report(status);
+                    self.hotspot.refresh();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+
+    /// Refresh the hotspot state and show the hotspot view.
+    pub fn open_hotspot(&mut self) {
+        self.hotspot.refresh();
+        self.flags.show_hotspot = true;
+    }
+
+    /// Hide the hotspot view.
+    pub fn close_hotspot(&mut self) {
+        self.flags.show_hotspot = false;
+    }
+}
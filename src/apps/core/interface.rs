@@ -0,0 +1,157 @@
+use super::App;
+use crate::error::Result;
+use crate::utils::interface::{WifiInterface, wifi_interfaces};
+
+use crossterm::event::poll;
+use crossterm::event::{self, Event, KeyEvent};
+use std::{io, time::Duration};
+
+#[derive(Debug, Default)]
+pub struct Interfaces {
+    pub interfaces: Vec<WifiInterface>,
+    // index 0 is the "automatic" entry, the interfaces follow from index 1
+    pub selected_index: usize,
+}
+
+impl Interfaces {
+    // the list is emptied when nmcli fails, only the automatic entry is left
+    pub fn fetch_interfaces(&mut self) -> Result<()> {
+        let result = wifi_interfaces().map(|interfaces| self.interfaces = interfaces);
+        if result.is_err() {
+            self.interfaces.clear();
+        }
+        if self.selected_index > self.interfaces.len() {
+            self.selected_index = 0;
+        }
+        result
+    }
+}
+
+impl App {
+    /// Handle keyboard input while the interface selector is active.
+    ///
+    /// - 'q' or Esc: close the selector without changing the interface
+    /// - Ctrl+C: exit the application
+    /// - Enter: operate on the selected interface and rescan on it
+    /// - 'j'/'k' or Down/Up: move the selection
+    /// - Ctrl+R: refresh the list of interfaces
+    pub fn handle_interfaces(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('q'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_interfaces = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_interfaces = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('c'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.exit();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Enter,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.select_interface();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('j'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.update_selected_interface(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Down,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.update_selected_interface(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('k'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.update_selected_interface(-1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Up,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.update_selected_interface(-1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('r'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.refresh_interfaces();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+
+    /// Move the selected interface by `direction`, wrapping around the automatic entry and the
+    /// discovered interfaces.
+    pub fn update_selected_interface(&mut self, direction: isize) {
+        let len = self.interfaces.interfaces.len() + 1;
+        self.interfaces.selected_index = ((self.interfaces.selected_index as isize + direction)
+            .rem_euclid(len as isize)) as usize;
+    }
+
+    /// Re-read the wireless interfaces, showing the error popup when nmcli fails.
+    pub fn refresh_interfaces(&mut self) {
+        if let Err(e) = self.interfaces.fetch_interfaces() {
+            self.show_error(e);
+        }
+    }
+
+    /// Refresh the wireless interfaces and show the selector with the current one selected.
+    pub fn open_interfaces(&mut self) {
+        self.refresh_interfaces();
+        self.interfaces.selected_index = self
+            .ifname
+            .as_ref()
+            .and_then(|ifname| {
+                self.interfaces
+                    .interfaces
+                    .iter()
+                    .position(|interface| &interface.name == ifname)
+            })
+            .map_or(0, |index| index + 1);
+        self.flags.show_interfaces = true;
+    }
+
+    /// Use the selected interface for every following nmcli call and rescan the networks on it.
+    fn select_interface(&mut self) {
+        self.ifname = match self.interfaces.selected_index {
+            0 => None,
+            index => self
+                .interfaces
+                .interfaces
+                .get(index - 1)
+                .map(|interface| interface.name.clone()),
+        };
+        self.flags.show_interfaces = false;
+        self.reset_selection();
+        self.scan();
+    }
+}
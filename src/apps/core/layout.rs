@@ -0,0 +1,110 @@
+use ratatui::layout::{Position, Rect};
+
+// below this size the ui does not fit and a message asking for a bigger terminal is shown instead
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 12;
+
+// popups stop growing past this width, long lines are hard to follow on very wide terminals
+const MAX_POPUP_WIDTH: u16 = 100;
+// dialogs and single line inputs only hold a short message
+const MIN_DIALOG_WIDTH: u16 = 36;
+const MAX_DIALOG_WIDTH: u16 = 70;
+
+pub fn too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+// A `width` x `height` rectangle centered in `area`, shrunk to fit when `area` is smaller
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+// A popup taking a share of `area`, but never smaller than the given minimum or wider than
+// MAX_POPUP_WIDTH
+pub fn popup(area: Rect, percent_x: u16, percent_y: u16, min_width: u16, min_height: u16) -> Rect {
+    let width = (area.width as u32 * percent_x as u32 / 100) as u16;
+    let height = (area.height as u32 * percent_y as u32 / 100) as u16;
+    centered(
+        area,
+        width.max(min_width).min(MAX_POPUP_WIDTH),
+        height.max(min_height),
+    )
+}
+
+// A bordered dialog with `lines` lines of content, like the confirmations and the inputs
+pub fn dialog(area: Rect, lines: u16) -> Rect {
+    let width = (area.width / 2).clamp(MIN_DIALOG_WIDTH, MAX_DIALOG_WIDTH);
+    centered(area, width, lines + 2)
+}
+
+// Columns a single line input is scrolled by so that the cursor stays inside of `width`
+pub fn input_scroll(width: u16, cursor_pos: u16) -> u16 {
+    cursor_pos.saturating_sub(width.saturating_sub(1))
+}
+
+// Where the terminal cursor goes in a bordered single line input drawn in `popup_area`
+pub fn input_cursor(popup_area: Rect, cursor_pos: u16) -> Position {
+    let width = popup_area.width.saturating_sub(2);
+    Position::new(
+        popup_area.x + 1 + cursor_pos - input_scroll(width, cursor_pos),
+        popup_area.y + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn centered_popup_is_clamped_to_the_screen() {
+        let area = Rect::new(0, 0, 30, 8);
+        assert_eq!(centered(area, 50, 20), area);
+        assert_eq!(centered(area, 10, 4), Rect::new(10, 2, 10, 4));
+    }
+
+    #[test]
+    fn popup_keeps_its_minimum_size() {
+        // a tenth of 24 rows would leave no room inside of the borders
+        let area = Rect::new(0, 0, 80, 24);
+        let popup = popup(area, 50, 10, 30, 5);
+        assert_eq!((popup.width, popup.height), (40, 5));
+    }
+
+    #[test]
+    fn popup_width_is_capped_on_wide_terminals() {
+        let area = Rect::new(0, 0, 400, 50);
+        let popup = popup(area, 66, 66, 40, 10);
+        assert_eq!(popup.width, MAX_POPUP_WIDTH);
+        assert_eq!(popup.x, 150);
+    }
+
+    #[test]
+    fn dialog_has_room_for_its_lines() {
+        assert_eq!(dialog(Rect::new(0, 0, 80, 24), 1).height, 3);
+        assert_eq!(dialog(Rect::new(0, 0, 80, 24), 2).width, 40);
+        assert_eq!(dialog(Rect::new(0, 0, 200, 24), 1).width, MAX_DIALOG_WIDTH);
+    }
+
+    #[test]
+    fn cursor_follows_the_rendered_input() {
+        let popup_area = Rect::new(20, 10, 12, 3);
+        assert_eq!(input_cursor(popup_area, 0), Position::new(21, 11));
+        assert_eq!(input_cursor(popup_area, 5), Position::new(26, 11));
+        // past the width the text scrolls and the cursor stays on the last column
+        assert_eq!(input_cursor(popup_area, 30), Position::new(30, 11));
+    }
+
+    #[test]
+    fn minimum_terminal_size() {
+        assert!(too_small(Rect::new(0, 0, 39, 30)));
+        assert!(too_small(Rect::new(0, 0, 80, 11)));
+        assert!(!too_small(Rect::new(0, 0, MIN_WIDTH, MIN_HEIGHT)));
+    }
+}
@@ -0,0 +1,113 @@
+use super::App;
+use super::notifications::ToastKind;
+use super::radio::RadioState;
+use crate::utils::connection_info::refresh_connection_info;
+use crate::utils::monitor::{MonitorEvent, NmcliMonitor, spawn_monitor};
+use crate::utils::scan::refresh_networks;
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// nmcli monitor prints a burst of lines for every activation, the list is refreshed at most this
+// often so that a single change does not start a dozen nmcli processes
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default)]
+pub struct Monitor {
+    nmcli: Option<NmcliMonitor>,
+    refresh_pending: bool,
+    // radio state read in the background after a change, taken over on the next frame
+    radio_update: Arc<RwLock<Option<RadioState>>>,
+    last_refresh: Option<Instant>,
+    // connection NetworkManager currently routes through, as last reported by nmcli monitor
+    pub active_connection: Option<String>,
+    // last state reported for each device
+    pub device_states: BTreeMap<String, String>,
+}
+
+impl Monitor {
+    pub fn start() -> Self {
+        Self {
+            nmcli: spawn_monitor(),
+            ..Default::default()
+        }
+    }
+}
+
+impl App {
+    /// Apply the state changes reported by `nmcli monitor` since the last frame.
+    ///
+    /// Device and primary connection changes are recorded and shown as a notification, and the
+    /// network list and status bar are refreshed in the background so they follow connections
+    /// made or dropped outside of nmtui.
+    pub fn process_monitor_events(&mut self) {
+        // the list can shrink under the selection when it is refreshed in the background
+        let len = self.wifi_list.read().map(|list| list.len()).unwrap_or(0);
+        if self.selected >= len {
+            self.selected = len.saturating_sub(1);
+        }
+
+        if let Some(radio) = self
+            .monitor
+            .radio_update
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            self.radio = radio;
+        }
+
+        let Some(nmcli) = &self.monitor.nmcli else {
+            return;
+        };
+        let events: Vec<MonitorEvent> = nmcli.events.try_iter().collect();
+
+        for event in events {
+            if let Some(message) = event.notification() {
+                self.notifications.push(ToastKind::Info, message);
+            }
+            match event {
+                MonitorEvent::DeviceState { device, state } => {
+                    if let Some(interface) = self
+                        .interfaces
+                        .interfaces
+                        .iter_mut()
+                        .find(|interface| interface.name == device)
+                    {
+                        interface.state = state.clone();
+                    }
+                    self.monitor.device_states.insert(device, state);
+                }
+                MonitorEvent::PrimaryConnection(connection) => {
+                    self.monitor.active_connection = connection;
+                }
+                MonitorEvent::Other(_) => {}
+            }
+            self.monitor.refresh_pending = true;
+        }
+
+        let refresh_due = self
+            .monitor
+            .last_refresh
+            .is_none_or(|last_refresh| last_refresh.elapsed() >= REFRESH_INTERVAL);
+        if self.monitor.refresh_pending && refresh_due {
+            self.monitor.refresh_pending = false;
+            self.monitor.last_refresh = Some(Instant::now());
+            // two nmcli calls, they are kept off the ui thread like the others
+            let radio_update = self.monitor.radio_update.clone();
+            thread::spawn(move || {
+                *radio_update.write().unwrap_or_else(PoisonError::into_inner) =
+                    Some(RadioState::current());
+            });
+            refresh_networks(
+                self.wifi_list.clone(),
+                self.signal_history.clone(),
+                self.ifname.clone(),
+                false,
+            );
+            refresh_connection_info(self.connection_info.clone(), self.ifname.clone());
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::App;
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+impl App {
+    /// Handle a mouse event on the network list.
+    ///
+    /// - left click: select the network under the pointer
+    /// - double click: connect to it, like Enter
+    /// - scroll wheel: move the selection
+    pub fn handle_network_mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let rows = self.mouse.network_rows.get();
+                let len = self.wifi_list.read().map(|list| list.len()).unwrap_or(0);
+                let Some(index) = rows
+                    .row_at(event.column, event.row)
+                    .filter(|&index| index < len)
+                else {
+                    return;
+                };
+                self.selected = index;
+                if self.mouse.is_double_click(event.column, event.row) {
+                    self.prepare_to_connect();
+                }
+            }
+            MouseEventKind::ScrollDown => self.update_selected_network(1),
+            MouseEventKind::ScrollUp => self.update_selected_network(-1),
+            _ => {}
+        }
+    }
+
+    /// Handle a mouse event on the saved connections list.
+    ///
+    /// - left click: select the connection under the pointer
+    /// - double click: connect with it, like Enter
+    /// - scroll wheel: move the selection
+    pub fn handle_saved_mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let rows = self.mouse.saved_rows.get();
+                if let Some(index) = rows
+                    .row_at(event.column, event.row)
+                    .filter(|&index| index < self.saved_connection.connections.len())
+                {
+                    self.saved_connection.selected_index = index;
+                    if self.mouse.is_double_click(event.column, event.row) {
+                        self.connect_saved_profile();
+                    }
+                }
+            }
+            MouseEventKind::ScrollDown => self.update_selected_saved_network(1),
+            MouseEventKind::ScrollUp => self.update_selected_saved_network(-1),
+            _ => {}
+        }
+    }
+}
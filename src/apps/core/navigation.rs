@@ -0,0 +1,70 @@
+use super::App;
+
+// Jumps through a list by more than one row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jump {
+    First,
+    Last,
+    PageUp,
+    PageDown,
+}
+
+impl Jump {
+    // Row selected after the jump in a list of `len` rows showing `page` rows at a time
+    //
+    // Unlike the single steps, the pages stop at the ends of the list instead of wrapping around.
+    pub fn apply(self, selected: usize, len: usize, page: usize) -> usize {
+        let last = len.saturating_sub(1);
+        let page = page.max(1);
+        match self {
+            Jump::First => 0,
+            Jump::Last => last,
+            Jump::PageUp => selected.saturating_sub(page),
+            Jump::PageDown => (selected + page).min(last),
+        }
+    }
+}
+
+impl App {
+    /// Move the selected network by a page or to either end of the list.
+    pub fn jump_network(&mut self, jump: Jump) {
+        let len = self.wifi_list.read().map(|list| list.len()).unwrap_or(0);
+        let page = self.mouse.network_rows.get().area.height as usize;
+        self.selected = jump.apply(self.selected, len, page);
+    }
+
+    /// Move the selected saved connection by a page or to either end of the list.
+    pub fn jump_saved_network(&mut self, jump: Jump) {
+        let len = self.saved_connection.connections.len();
+        let page = self.mouse.saved_rows.get().area.height as usize;
+        self.saved_connection.selected_index =
+            jump.apply(self.saved_connection.selected_index, len, page);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Jump;
+
+    #[test]
+    fn jumps_to_either_end() {
+        assert_eq!(Jump::First.apply(7, 20, 5), 0);
+        assert_eq!(Jump::Last.apply(7, 20, 5), 19);
+    }
+
+    #[test]
+    fn pages_stop_at_the_ends() {
+        assert_eq!(Jump::PageDown.apply(7, 20, 5), 12);
+        assert_eq!(Jump::PageDown.apply(17, 20, 5), 19);
+        assert_eq!(Jump::PageUp.apply(7, 20, 5), 2);
+        assert_eq!(Jump::PageUp.apply(3, 20, 5), 0);
+    }
+
+    #[test]
+    fn empty_list_and_unknown_page_size() {
+        assert_eq!(Jump::Last.apply(0, 0, 5), 0);
+        assert_eq!(Jump::PageDown.apply(0, 0, 5), 0);
+        // before the first frame the page size is not known yet, a page is then a single row
+        assert_eq!(Jump::PageDown.apply(3, 20, 0), 4);
+    }
+}
@@ -0,0 +1,172 @@
+use super::App;
+use crate::apps::handlers::status::Status;
+use crate::utils::logger;
+
+use crossterm::event::poll;
+use crossterm::event::{self, Event, KeyEvent};
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+// how long a toast stays on the screen
+pub const TOAST_TIMEOUT: Duration = Duration::from_secs(4);
+
+// toasts shown at once, the older ones are only in the history
+pub const VISIBLE_TOASTS: usize = 3;
+
+// number of notifications kept for the history view
+const HISTORY_LENGTH: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Failure,
+    // changes made outside of nmtui, reported by nmcli monitor
+    Info,
+}
+
+#[derive(Debug)]
+pub struct Toast {
+    pub message: String,
+    pub kind: ToastKind,
+    pub shown_at: Instant,
+}
+
+impl Toast {
+    pub fn expired(&self) -> bool {
+        self.shown_at.elapsed() >= TOAST_TIMEOUT
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Notifications {
+    // newest last
+    history: VecDeque<Toast>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, kind: ToastKind, message: String) {
+        if self.history.len() == HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+        self.history.push_back(Toast {
+            message,
+            kind,
+            shown_at: Instant::now(),
+        });
+    }
+
+    // The toasts that have not expired yet, oldest first
+    pub fn visible(&self) -> impl Iterator<Item = &Toast> {
+        let skip = self.history.len().saturating_sub(VISIBLE_TOASTS);
+        self.history
+            .iter()
+            .skip(skip)
+            .filter(|toast| !toast.expired())
+    }
+
+    // Every notification kept, newest first
+    pub fn history(&self) -> impl Iterator<Item = &Toast> {
+        self.history.iter().rev()
+    }
+}
+
+impl App {
+    /// Report the result of an action.
+    ///
+    /// Failures that come with something for the user to do open the status popup, everything
+    /// else is shown as a toast that goes away on its own.
+    pub fn report(&mut self, status: Status) {
+        if status.needs_attention() {
+            self.wifi_credentials.status = status;
+            self.wifi_credentials.flags.show_status_popup = true;
+            return;
+        }
+        let kind = if status.status_code.success() {
+            ToastKind::Success
+        } else {
+            logger::warn(status.status_message.trim());
+            ToastKind::Failure
+        };
+        // nmcli errors can span several lines, the toast only has room for the first one
+        let message = status
+            .status_message
+            .trim()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        self.notifications.push(kind, message);
+    }
+
+    /// Handle keyboard input while the notification history is open.
+    ///
+    /// - 'q', 'm' or Esc: close the history
+    /// - Ctrl+C: exit the application
+    pub fn handle_notification_history(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('q'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_notifications = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('m'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_notifications = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_notifications = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('c'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.exit();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_latest_toasts_are_visible() {
+        let mut notifications = Notifications::default();
+        for i in 0..5 {
+            notifications.push(ToastKind::Success, format!("toast {}", i));
+        }
+        let visible: Vec<&str> = notifications
+            .visible()
+            .map(|toast| toast.message.as_str())
+            .collect();
+        assert_eq!(visible, ["toast 2", "toast 3", "toast 4"]);
+        assert_eq!(notifications.history().next().unwrap().message, "toast 4");
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut notifications = Notifications::default();
+        for i in 0..HISTORY_LENGTH + 10 {
+            notifications.push(ToastKind::Info, format!("toast {}", i));
+        }
+        assert_eq!(notifications.history().count(), HISTORY_LENGTH);
+        assert_eq!(notifications.history().last().unwrap().message, "toast 10");
+    }
+}
@@ -0,0 +1,147 @@
+use super::App;
+use crate::apps::handlers::status::Status;
+use crate::apps::handlers::utils::{delete_char, enter_char, move_cursor_right};
+use crate::utils::profile::{clone_profile, free_name, rename_profile};
+use crate::utils::saved_connection::profile_names;
+
+use crossterm::event::poll;
+use crossterm::event::{self, Event, KeyEvent};
+use std::io;
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
+use std::time::Duration;
+
+// What the name typed into the profile name popup is used for
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileEdit {
+    #[default]
+    Rename,
+    Clone,
+}
+
+impl App {
+    /// Show the popup asking for the new name of the selected saved profile, or of its copy.
+    ///
+    /// A rename starts from the current name, a clone from the first free "NAME 2", "NAME 3", ...
+    pub fn open_profile_name_popup(&mut self, edit: ProfileEdit) {
+        let saved = &mut self.saved_connection;
+        let Some(connection) = saved.connections.get(saved.selected_index) else {
+            return;
+        };
+        saved.name_input = match edit {
+            ProfileEdit::Rename => connection.name.clone(),
+            ProfileEdit::Clone => free_name(
+                &connection.name,
+                saved.connections.iter().map(|c| c.name.as_str()),
+            ),
+        };
+        saved.cursor_pos = saved.name_input.chars().count() as u16;
+        saved.name_edit = edit;
+        self.flags.show_profile_name_popup = true;
+    }
+
+    /// Handle keyboard input for the popup asking for the name of a saved profile.
+    ///
+    /// Enter renames or clones the selected profile and reports the result, a name another
+    /// profile already uses is refused and the popup stays open to change it. Esc cancels.
+    pub fn handle_profile_name_input(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            let saved = &mut self.saved_connection;
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Left,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    saved.cursor_pos = saved.cursor_pos.saturating_sub(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Right,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    move_cursor_right(&saved.name_input, &mut saved.cursor_pos);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_profile_name_popup = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char(c),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    enter_char(&mut saved.name_input, c, &saved.cursor_pos);
+                    move_cursor_right(&saved.name_input, &mut saved.cursor_pos);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Backspace,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    delete_char(&mut saved.name_input, &mut saved.cursor_pos);
+                    saved.cursor_pos = saved.cursor_pos.saturating_sub(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Enter,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !saved.name_input.trim().is_empty() => {
+                    self.save_profile_name();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+
+    fn save_profile_name(&mut self) {
+        let saved = &self.saved_connection;
+        let Some(connection) = saved.connections.get(saved.selected_index) else {
+            self.flags.show_profile_name_popup = false;
+            return;
+        };
+        let new_name = saved.name_input.trim().to_string();
+        if saved.name_edit == ProfileEdit::Rename && new_name == connection.name {
+            self.flags.show_profile_name_popup = false;
+            return;
+        }
+        // nmcli accepts duplicate names, but then `id NAME` no longer tells the profiles apart.
+        // The saved list only has the wifi profiles, a vpn or ethernet one can hold the name too.
+        let taken = match profile_names() {
+            Ok(names) => names.contains(&new_name),
+            Err(e) => {
+                self.report(e.into());
+                return;
+            }
+        };
+        if taken {
+            self.report(Status::new(
+                format!("A saved profile is already named '{}'", new_name),
+                ExitStatus::from_raw(1 << 8),
+            ));
+            return;
+        }
+        let status = match saved.name_edit {
+            ProfileEdit::Rename => rename_profile(&connection.uuid, &connection.name, &new_name),
+            ProfileEdit::Clone => clone_profile(&connection.uuid, &connection.name, &new_name),
+        };
+        let success = status.status_code.success();
+        self.report(status);
+        self.flags.show_profile_name_popup = false;
+        self.refresh_saved_connections();
+        // the selection follows the renamed profile or the new copy
+        if success
+            && let Some(index) = self
+                .saved_connection
+                .connections
+                .iter()
+                .position(|c| c.name == new_name)
+        {
+            self.saved_connection.selected_index = index;
+        }
+    }
+}
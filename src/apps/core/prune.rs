@@ -0,0 +1,139 @@
+use super::App;
+use super::saved_connection::Connections;
+
+use crossterm::event::poll;
+use crossterm::event::{self, Event, KeyEvent};
+use std::io;
+use std::time::{Duration, SystemTime};
+
+pub const DEFAULT_PRUNE_DAYS: u64 = 90;
+
+// periods the preview steps through with the arrow keys, in days
+const PRUNE_STEPS: [u64; 6] = [7, 30, 90, 180, 365, 730];
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+impl App {
+    /// Saved connections that were last used more than `prune_days` days ago.
+    ///
+    /// Profiles that were never used and the hotspot profile are left out, they are not stale
+    /// networks from a trip but ones that were just created.
+    pub fn stale_connections(&self) -> Vec<&Connections> {
+        let now = SystemTime::now();
+        // --prune-after takes any number of days, a huge one keeps everything
+        let max_age = Duration::from_secs(self.prune_days.saturating_mul(DAY_SECS));
+        self.saved_connection
+            .connections
+            .iter()
+            .filter(|connection| !connection.is_hotspot)
+            .filter(|connection| {
+                connection.last_used.is_some_and(|last_used| {
+                    now.duration_since(last_used).unwrap_or_default() > max_age
+                })
+            })
+            .collect()
+    }
+
+    /// Handle keyboard input while the prune preview is shown over the saved list.
+    ///
+    /// - Left/Right or '-'/'+': shorten or lengthen the period
+    /// - Enter: mark the stale connections and ask to delete them
+    /// - 'q' or Esc: close the preview without marking anything
+    /// - Ctrl+C: exit the application
+    pub fn handle_prune(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Left,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.shorten_prune_period();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('-'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.shorten_prune_period();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Right,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.lengthen_prune_period();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('+'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.lengthen_prune_period();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Enter,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.select_stale_connections();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('q'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_prune = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_prune = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('c'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.exit();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+
+    fn shorten_prune_period(&mut self) {
+        if let Some(&days) = PRUNE_STEPS
+            .iter()
+            .rev()
+            .find(|&&days| days < self.prune_days)
+        {
+            self.prune_days = days;
+        }
+    }
+
+    fn lengthen_prune_period(&mut self) {
+        if let Some(&days) = PRUNE_STEPS.iter().find(|&&days| days > self.prune_days) {
+            self.prune_days = days;
+        }
+    }
+
+    /// Mark the stale connections in the saved list and open the confirmation of the bulk delete.
+    fn select_stale_connections(&mut self) {
+        let stale: Vec<String> = self
+            .stale_connections()
+            .iter()
+            .map(|connection| connection.uuid.clone())
+            .collect();
+        self.flags.show_prune = false;
+        if stale.is_empty() {
+            return;
+        }
+        self.saved_connection.marked = stale.into_iter().collect();
+        self.flags.show_delete_confirmation = true;
+    }
+}
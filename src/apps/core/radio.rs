@@ -0,0 +1,132 @@
+use super::App;
+use crate::apps::handlers::mouse::Button;
+use crate::utils::radio::{networking_enabled, set_networking, set_wifi_radio, wifi_radio_enabled};
+
+use crossterm::event::KeyEventKind::Press;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, poll};
+use std::io;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct RadioState {
+    pub wifi_enabled: bool,
+    pub networking_enabled: bool,
+}
+
+impl Default for RadioState {
+    // assume everything is on until nmcli says otherwise so that nothing flashes on startup
+    fn default() -> Self {
+        Self {
+            wifi_enabled: true,
+            networking_enabled: true,
+        }
+    }
+}
+
+impl RadioState {
+    pub fn current() -> Self {
+        Self {
+            wifi_enabled: wifi_radio_enabled(),
+            networking_enabled: networking_enabled(),
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        *self = Self::current();
+    }
+}
+
+impl App {
+    /// Turn the Wi-Fi radio on or off and rescan so the list matches the new state.
+    pub fn toggle_wifi_radio(&mut self) {
+        let status = set_wifi_radio(!self.radio.wifi_enabled);
+        self.report(status);
+        self.radio.refresh();
+        self.reset_selection();
+        self.scan();
+    }
+
+    /// Handle the confirmation popup shown before turning networking on or off.
+    ///
+    /// Enter, 'Y' or 'y' toggles networking; 'N', 'n', Esc or 'q' cancels; Ctrl-C exits.
+    pub fn handle_networking_confirmation(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Enter,
+                    kind: Press,
+                    ..
+                }) => {
+                    self.toggle_networking();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('Y'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.toggle_networking();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('y'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.toggle_networking();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('N'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.flags.show_networking_confirmation = false;
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('n'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.flags.show_networking_confirmation = false;
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c'),
+                    kind: Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.exit();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Esc,
+                    kind: Press,
+                    ..
+                }) => {
+                    self.flags.show_networking_confirmation = false;
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('q'),
+                    kind: Press,
+                    ..
+                }) => {
+                    self.flags.show_networking_confirmation = false;
+                }
+                Event::Mouse(mouse) => match self.mouse.clicked_button(&mouse) {
+                    Some(Button::Yes) => self.toggle_networking(),
+                    Some(Button::No) => self.flags.show_networking_confirmation = false,
+                    _ => {}
+                },
+                _ => {}
+            }
+        };
+        Ok(())
+    }
+
+    /// Turn networking as a whole on or off and rescan so the list matches the new state.
+    fn toggle_networking(&mut self) {
+        let status = set_networking(!self.radio.networking_enabled);
+        self.report(status);
+        self.flags.show_networking_confirmation = false;
+        self.radio.refresh();
+        self.reset_selection();
+        self.scan();
+    }
+}
@@ -1,4 +1,5 @@
 use super::App;
+use crate::utils::logger;
 use ratatui::DefaultTerminal;
 
 impl App {
@@ -23,6 +24,11 @@ impl App {
         terminal: &mut DefaultTerminal,
     ) -> Result<(), Box<dyn std::error::Error>> {
         while !self.app_state.exit {
+            self.process_monitor_events();
+            self.process_background_errors();
+            self.process_portal_checks();
+            self.sample_signal_history();
+            self.log_view_transition();
             terminal.draw(|frame| self.draw(frame))?;
             // NOTE: here placement is is key because we want to prioritize which popup gets handled first
             // If  there is a preceeding popup shown, we want to handle the one that is on top
@@ -37,6 +43,65 @@ impl App {
             else if self.flags.show_delete_confirmation {
                 self.handle_delete_confirmation()?;
             }
+            // to handle the confirmation before turning networking on or off
+            else if self.flags.show_networking_confirmation {
+                self.handle_networking_confirmation()?;
+            }
+            // to handle the popup asking for the secrets of a vpn connection
+            else if self.flags.show_vpn_secret_popup {
+                self.handle_vpn_secret_input()?;
+            }
+            // to handle the popup asking for the path of a vpn config to import
+            else if self.flags.show_vpn_import_popup {
+                self.handle_vpn_import_input()?;
+            }
+            // to handle the popup asking for the new name of a saved profile
+            else if self.flags.show_profile_name_popup {
+                self.handle_profile_name_input()?;
+            }
+            // to handle the status message popup, this is above the lists because the result
+            // of an action in the vpn list is shown on top of it
+            else if self.wifi_credentials.flags.show_status_popup {
+                self.wifi_credentials.handle_status_message(&self.mouse)?;
+            }
+            // to handle the wifi password input popup, this is above the lists because a password
+            // rejected from the saved list is entered again on top of it
+            else if self.wifi_credentials.flags.show_password_popup {
+                if let Some(status) = self
+                    .wifi_credentials
+                    .handle_password_input(self.ifname.as_deref())?
+                {
+                    self.report_connection(status);
+                }
+            }
+            // to handle the diagnostics view
+            else if self.flags.show_diagnostics {
+                self.handle_diagnostics()?;
+            }
+            // to handle the notification history
+            else if self.flags.show_notifications {
+                self.handle_notification_history()?;
+            }
+            // to handle the channel congestion view
+            else if self.flags.show_channels {
+                self.handle_channels()?;
+            }
+            // to handle the interface selector popup
+            else if self.flags.show_interfaces {
+                self.handle_interfaces()?;
+            }
+            // to handle the hotspot popup
+            else if self.flags.show_hotspot {
+                self.handle_hotspot()?;
+            }
+            // to handle the vpn connections popup
+            else if self.flags.show_vpn {
+                self.handle_vpn()?;
+            }
+            // to handle the preview of the stale saved connections, shown over the saved list
+            else if self.flags.show_prune {
+                self.handle_prune()?;
+            }
             // to handle the saved connections popup
             else if self.flags.show_saved {
                 self.handle_saved()?;
@@ -45,14 +110,6 @@ impl App {
             else if self.wifi_credentials.flags.show_ssid_popup {
                 self.wifi_credentials.handle_ssid_input()?;
             }
-            //to handle the wifi password input popups
-            else if self.wifi_credentials.flags.show_password_popup {
-                self.wifi_credentials.handle_password_input()?;
-            }
-            // to handle the status message popup
-            else if self.wifi_credentials.flags.show_status_popup {
-                self.wifi_credentials.handle_status_message()?;
-            }
             // to handle the main events
             else {
                 self.handle_events()?;
@@ -60,4 +117,54 @@ impl App {
         }
         Ok(())
     }
+
+    /// Name of the view that receives input, following the same priority as [`App::run`].
+    fn current_view(&self) -> &'static str {
+        if self.flags.show_help {
+            "help"
+        } else if self.flags.show_delete_confirmation {
+            "delete confirmation"
+        } else if self.flags.show_networking_confirmation {
+            "networking confirmation"
+        } else if self.flags.show_vpn_secret_popup {
+            "vpn secret"
+        } else if self.flags.show_vpn_import_popup {
+            "vpn import"
+        } else if self.flags.show_profile_name_popup {
+            "profile name"
+        } else if self.wifi_credentials.flags.show_status_popup {
+            "status"
+        } else if self.wifi_credentials.flags.show_password_popup {
+            "password input"
+        } else if self.flags.show_diagnostics {
+            "diagnostics"
+        } else if self.flags.show_notifications {
+            "notifications"
+        } else if self.flags.show_channels {
+            "channels"
+        } else if self.flags.show_interfaces {
+            "interfaces"
+        } else if self.flags.show_hotspot {
+            "hotspot"
+        } else if self.flags.show_vpn {
+            "vpn"
+        } else if self.flags.show_prune {
+            "prune"
+        } else if self.flags.show_saved {
+            "saved connections"
+        } else if self.wifi_credentials.flags.show_ssid_popup {
+            "ssid input"
+        } else {
+            "networks"
+        }
+    }
+
+    /// Log a line whenever the view receiving input changes.
+    fn log_view_transition(&mut self) {
+        let view = self.current_view();
+        if view != self.view {
+            logger::info(&format!("view: {} -> {}", self.view, view));
+            self.view = view;
+        }
+    }
 }
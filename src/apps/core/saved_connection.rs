@@ -1,59 +1,136 @@
 use super::App;
+use super::navigation::Jump;
+use super::profile_name::ProfileEdit;
+use crate::error::Result;
+use crate::utils::age::parse_timestamp;
+use crate::utils::command::run_checked;
+use crate::utils::connect::activate_saved_profile;
+use crate::utils::saved_connection::wireless_settings;
+use crate::utils::terse::records;
 
 use crossterm::event::poll;
 use crossterm::event::{self, Event, KeyEvent};
+use ratatui::widgets::TableState;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
 use std::process::Command;
+use std::sync::PoisonError;
+use std::time::SystemTime;
 use std::{io, time::Duration};
 
 #[derive(Debug)]
 pub struct Connections {
+    // name of the profile, not necessarily the one of its network
+    pub name: String,
+    // network the profile joins (802-11-wireless.ssid), matched against the scan
     pub ssid: String,
-    pub last_used: String,
+    pub uuid: String,
+    // None for a profile that was never used
+    pub last_used: Option<SystemTime>,
+    // an access point profile (802-11-wireless.mode ap), like the one of the hotspot view
+    pub is_hotspot: bool,
 }
 
 #[derive(Debug, Default)]
 pub struct SavedConnections {
     pub connections: Vec<Connections>,
     pub selected_index: usize,
+    // scroll position of the list, updated while rendering so it is kept between frames
+    pub table_state: RefCell<TableState>,
+    // uuids of the connections marked for a bulk delete, several profiles can share a name
+    pub marked: BTreeSet<String>,
+    // name typed into the rename or clone popup
+    pub name_input: String,
+    pub cursor_pos: u16,
+    pub name_edit: ProfileEdit,
 }
 
 impl SavedConnections {
-    // nmcli -t -f NAME,TYPE,TIMESTAMP-REAL connection show
-    pub fn fetch_saved_connections(&mut self) {
-        let output = Command::new("nmcli")
-            .args(["-t", "-f", "NAME,TYPE,TIMESTAMP-REAL", "connection", "show"])
-            .output()
-            .expect(" Failed to execute nmcli command");
+    // nmcli -t -f NAME,UUID,TYPE,TIMESTAMP connection show
+    // and the wireless settings of the listed profiles
+    pub fn fetch_saved_connections(&mut self) -> Result<()> {
+        let output = run_checked(Command::new("nmcli").args([
+            "-t",
+            "-f",
+            "NAME,UUID,TYPE,TIMESTAMP",
+            "connection",
+            "show",
+        ]))?;
 
         // this will store the connections
         let mut connections: Vec<Connections> = Vec::new();
-        if !output.status.success() {
-            self.connections = connections;
-            return;
-        }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
 
-        for line in stdout.lines() {
-            // there is some thing like \: in the time stamp, so we limit split to 3 parts
-            let mut parts = line.splitn(3, ':');
-            let ssid = parts.next().unwrap_or("").to_string();
+        let profiles: Vec<[String; 4]> = records::<4>(&stdout)?
+            .into_iter()
+            .filter(|[name, _, connection_type, _]| {
+                !name.is_empty() && connection_type == "802-11-wireless"
+            })
+            .collect();
+        let uuids: Vec<String> = profiles
+            .iter()
+            .map(|[_, uuid, _, _]| uuid.clone())
+            .collect();
+        let settings = wireless_settings(&uuids)?;
 
-            // this is the connection type not the security type
-            let connection_type = parts.next().unwrap_or("").to_string();
+        for [name, uuid, _, last_used] in profiles {
+            let settings = settings.get(&uuid).cloned().unwrap_or_default();
+            // any access point profile, not only the one of the hotspot view, a network that
+            // happens to be named like it is a client profile
+            let is_hotspot = settings.mode == "ap";
+            connections.push(Connections {
+                name,
+                ssid: settings.ssid,
+                uuid,
+                last_used: parse_timestamp(&last_used),
+                is_hotspot,
+            });
+        }
+        // marks of connections that are gone are dropped
+        self.marked.retain(|uuid| {
+            connections
+                .iter()
+                .any(|connection| &connection.uuid == uuid)
+        });
+        // keep the selection in bounds when connections disappear between refreshes
+        if self.selected_index >= connections.len() {
+            self.selected_index = connections.len().saturating_sub(1);
+        }
+        self.connections = connections;
+        Ok(())
+    }
 
-            let last_used = parts
-                .next()
-                .unwrap_or("")
-                .chars()
-                .filter(|&c| c != '\\')
-                .collect();
+    // Mark or unmark the selected connection
+    pub fn toggle_mark(&mut self) {
+        let Some(connection) = self.connections.get(self.selected_index) else {
+            return;
+        };
+        if !self.marked.remove(&connection.uuid) {
+            self.marked.insert(connection.uuid.clone());
+        }
+    }
 
-            if !ssid.is_empty() && connection_type == "802-11-wireless" {
-                connections.push(Connections { ssid, last_used });
-            }
+    // Mark every connection, or none when all of them are marked already
+    pub fn mark_all(&mut self) {
+        if self.marked.len() == self.connections.len() {
+            self.marked.clear();
+        } else {
+            self.marked = self
+                .connections
+                .iter()
+                .map(|connection| connection.uuid.clone())
+                .collect();
         }
-        self.connections = connections;
+    }
+
+    pub fn invert_marks(&mut self) {
+        self.marked = self
+            .connections
+            .iter()
+            .map(|connection| connection.uuid.clone())
+            .filter(|uuid| !self.marked.contains(uuid))
+            .collect();
     }
 }
 
@@ -62,10 +139,19 @@ impl App {
     ///
     /// Recognizes key presses and performs the following actions:
     /// - 'q' or Esc: close the saved-connections view
+    /// - Enter: connect with the selected profile
     /// - Ctrl+C: exit the application
-    /// - 'd': show the delete-confirmation dialog
+    /// - 'd': show the delete-confirmation dialog, for the marked connections when there are any
+    /// - Space: mark or unmark the selected connection and move to the next one
+    /// - 'a': mark all connections, or none when all are marked; 'i': invert the marks
+    /// - 'p': preview the connections unused for a while, to delete them at once
+    /// - 'r': rename the selected profile; 'c': clone it under a new name
+    /// - 'u': restore the connection deleted last
     /// - 'j' or Down: advance the saved-connection selection by one
     /// - 'k' or Up: move the saved-connection selection back by one
+    /// - PageUp/PageDown: move the selection by a page
+    /// - Home or 'g', End or 'G': select the first or last saved connection
+    /// - mouse: click to select, scroll to move the selection
     /// - 'h' or '?': show the help view
     /// - Ctrl+R: refresh the saved connections list by re-fetching saved connections
     ///
@@ -108,13 +194,56 @@ impl App {
                     self.exit();
                 }
                 Event::Key(KeyEvent {
-                    code: event::KeyCode::Char('d'),
+                    code: event::KeyCode::Enter,
                     kind: event::KeyEventKind::Press,
                     ..
                 }) => {
+                    self.connect_saved_profile();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('d'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !self.saved_connection.connections.is_empty() => {
                     // this will evaluate to run the delete confirmation dialog from the core ui
                     self.flags.show_delete_confirmation = true;
                 }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char(' '),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.saved_connection.toggle_mark();
+                    self.update_selected_saved_network(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('a'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.saved_connection.mark_all();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('i'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.saved_connection.invert_marks();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('p'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_prune = true;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('u'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.undo_delete();
+                }
 
                 Event::Key(KeyEvent {
                     code: event::KeyCode::Char('j'),
@@ -130,6 +259,48 @@ impl App {
                 }) => {
                     self.update_selected_saved_network(1);
                 }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Home,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.jump_saved_network(Jump::First);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('g'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.jump_saved_network(Jump::First);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::End,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.jump_saved_network(Jump::Last);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('G'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.jump_saved_network(Jump::Last);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::PageUp,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.jump_saved_network(Jump::PageUp);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::PageDown,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.jump_saved_network(Jump::PageDown);
+                }
                 Event::Key(KeyEvent {
                     code: event::KeyCode::Char('k'),
                     kind: event::KeyEventKind::Press,
@@ -164,7 +335,25 @@ impl App {
                     modifiers: event::KeyModifiers::CONTROL,
                     ..
                 }) => {
-                    self.saved_connection.fetch_saved_connections();
+                    self.refresh_saved_connections();
+                }
+                // after Ctrl+R and Ctrl+C, which would match these as well
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('r'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.open_profile_name_popup(ProfileEdit::Rename);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('c'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.open_profile_name_popup(ProfileEdit::Clone);
+                }
+                Event::Mouse(mouse) => {
+                    self.handle_saved_mouse(mouse);
                 }
                 _ => {}
             };
@@ -197,6 +386,46 @@ impl App {
                     .rem_euclid(len as isize)) as usize;
         }
     }
+    /// Bring up the selected saved profile, whether or not the last scan saw its network.
+    ///
+    /// The scan list and the saved list are refreshed afterwards, for the in-use marker and the
+    /// last used time.
+    pub fn connect_saved_profile(&mut self) {
+        let Some(connection) = self
+            .saved_connection
+            .connections
+            .get(self.saved_connection.selected_index)
+        else {
+            return;
+        };
+        // remembered so that the password can be entered again if the saved one is rejected
+        self.wifi_credentials.ssid = connection.ssid.clone();
+        self.wifi_credentials.profile = Some((connection.name.clone(), connection.uuid.clone()));
+        let status =
+            activate_saved_profile(&connection.name, &connection.uuid, self.ifname.as_deref());
+        self.report_connection(status);
+        self.refresh_saved_connections();
+        self.scan();
+    }
+
+    /// Signal of the network of a saved profile in the latest scan, None when it is out of range.
+    pub fn saved_network_signal(&self, ssid: &str) -> Option<u8> {
+        self.wifi_list
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|network| network.ssid == ssid)
+            .map(|network| network.signal)
+            .max()
+    }
+
+    /// Re-read the saved connections, showing the error popup when nmcli fails.
+    pub fn refresh_saved_connections(&mut self) {
+        if let Err(e) = self.saved_connection.fetch_saved_connections() {
+            self.show_error(e);
+        }
+    }
+
     /// Opens the saved-connections view.
     ///
     /// This refreshes the stored list of saved Wi‑Fi connections and makes the saved-connections UI visible.
@@ -210,7 +439,7 @@ impl App {
     /// assert!(app.flags.show_saved);
     /// ```
     pub fn open_saved_list(&mut self) {
-        self.saved_connection.fetch_saved_connections();
+        self.refresh_saved_connections();
         self.flags.show_saved = true;
     }
 
@@ -0,0 +1,38 @@
+use super::App;
+use crate::utils::scan::refresh_networks;
+
+use std::time::{Duration, Instant};
+
+// how often a fresh scan is requested while the signal panel is open
+const SIGNAL_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+impl App {
+    /// Show or hide the signal history panel next to the network list.
+    pub fn toggle_signal_panel(&mut self) {
+        self.flags.show_signal_panel = !self.flags.show_signal_panel;
+        // sample right away so the panel does not start empty
+        self.last_signal_sample = None;
+    }
+
+    /// Request a fresh scan every few seconds while the signal panel is open.
+    ///
+    /// Scanning keeps the radio busy, so no periodic scans are made while the panel is closed;
+    /// the history still grows with every scan made for other reasons.
+    pub fn sample_signal_history(&mut self) {
+        if !self.flags.show_signal_panel || !self.radio.wifi_enabled {
+            return;
+        }
+        let sample_due = self
+            .last_signal_sample
+            .is_none_or(|last_sample| last_sample.elapsed() >= SIGNAL_SAMPLE_INTERVAL);
+        if sample_due {
+            self.last_signal_sample = Some(Instant::now());
+            refresh_networks(
+                self.wifi_list.clone(),
+                self.signal_history.clone(),
+                self.ifname.clone(),
+                true,
+            );
+        }
+    }
+}
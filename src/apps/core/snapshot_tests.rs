@@ -0,0 +1,412 @@
+use super::App;
+use super::notifications::ToastKind;
+use super::profile_name::ProfileEdit;
+use super::saved_connection::Connections;
+use crate::WifiNetwork;
+use crate::apps::handlers::status::Status;
+use crate::utils::connection_info::{ConnectionInfo, Connectivity};
+use crate::utils::diagnostics::{Check, CheckResult, Outcome};
+use crate::utils::failure::FailureKind;
+
+use insta::assert_snapshot;
+use ratatui::{Terminal, backend::TestBackend, layout::Position};
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 24;
+
+// ages are given a little more than whole days, so that they do not change during the test
+const DAY: u64 = 24 * 60 * 60 + 60;
+
+// frames of the scanning spinner, replaced by a fixed one since the frame depends on the clock
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+fn network(ssid: &str, signal: u8, security: &str, is_saved: bool, in_use: bool) -> WifiNetwork {
+    WifiNetwork {
+        is_saved,
+        in_use,
+        ssid: ssid.to_string(),
+        security: security.to_string(),
+        bssid: format!("AA:BB:CC:DD:EE:{:02X}", signal),
+        signal,
+        channel: 6,
+        frequency: 2437,
+    }
+}
+
+// An app with a fixed list of networks that never calls nmcli
+fn app() -> App {
+    let networks = vec![
+        network("Home", 82, "WPA2", true, true),
+        network("Coffee:Shop", 64, "Unsecured", false, false),
+        network("Neighbour 5G", 41, "WPA1 WPA2", false, false),
+        network("Office", 23, "WPA3", true, false),
+        WifiNetwork {
+            is_saved: false,
+            in_use: false,
+            ssid: "Connect to Hidden network".to_string(),
+            security: "?".to_string(),
+            bssid: String::new(),
+            signal: 0,
+            channel: 0,
+            frequency: 0,
+        },
+    ];
+    let (errors, error_receiver) = mpsc::channel();
+    let (portals, portal_receiver) = mpsc::channel();
+    App {
+        wifi_credentials: Default::default(),
+        wifi_list: Arc::new(RwLock::new(networks)),
+        selected: 0,
+        app_state: Default::default(),
+        view: "networks",
+        saved_connection: Default::default(),
+        vpn_connection: Default::default(),
+        hotspot: Default::default(),
+        interfaces: Default::default(),
+        radio: Default::default(),
+        monitor: Default::default(),
+        notifications: Default::default(),
+        deleted_profile: None,
+        signal_history: Default::default(),
+        last_signal_sample: None,
+        connection_info: Default::default(),
+        ifname: None,
+        portal_browser: None,
+        portal_url: None,
+        portals,
+        portal_receiver,
+        diagnostics: Default::default(),
+        netns: None,
+        prune_days: 90,
+        errors,
+        error_receiver,
+        mouse: Default::default(),
+        network_table_state: Default::default(),
+        cursor: Default::default(),
+        flags: Default::default(),
+    }
+}
+
+fn render(app: &App) -> String {
+    render_sized(app, WIDTH, HEIGHT)
+}
+
+fn render_sized(app: &App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|frame| frame.render_widget(app, frame.area()))
+        .unwrap();
+    terminal
+        .backend()
+        .to_string()
+        .chars()
+        .map(|c| if SPINNER.contains(&c) { '⠿' } else { c })
+        .collect()
+}
+
+#[test]
+fn scanning() {
+    let app = app();
+    app.flags.is_scanning.store(true, Ordering::SeqCst);
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn network_list() {
+    let mut app = app();
+    app.selected = 1;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn help() {
+    let mut app = app();
+    app.flags.show_help = true;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn saved_connections() {
+    let mut app = app();
+    app.saved_connection.connections = vec![
+        Connections {
+            name: "Home".to_string(),
+            ssid: "Home".to_string(),
+            uuid: "5d1c7f36-6a39-4c5b-9c1f-7a4f0e6a1111".to_string(),
+            last_used: Some(SystemTime::now() - Duration::from_secs(3 * DAY)),
+            is_hotspot: false,
+        },
+        Connections {
+            name: "Hotspot".to_string(),
+            ssid: "Hotspot".to_string(),
+            uuid: "0b7c9a53-3c1e-4d8e-9a39-2f6a4f0e6a11".to_string(),
+            last_used: None,
+            is_hotspot: true,
+        },
+        // a profile named apart from its network is still in range
+        Connections {
+            name: "Work".to_string(),
+            ssid: "Office".to_string(),
+            uuid: "7e0f3a2b-1c4d-4e5f-8a9b-0c1d2e3f4a5b".to_string(),
+            last_used: Some(SystemTime::now() - Duration::from_secs(10 * DAY)),
+            is_hotspot: false,
+        },
+    ];
+    app.saved_connection.selected_index = 1;
+    app.flags.show_saved = true;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn delete_confirmation() {
+    let mut app = app();
+    app.selected = 3;
+    app.flags.show_delete_confirmation = true;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn password_popup() {
+    let mut app = app();
+    app.selected = 2;
+    app.wifi_credentials.ssid = "Neighbour 5G".to_string();
+    app.wifi_credentials.password = "hunter22".to_string();
+    app.wifi_credentials.flags.show_password_popup = true;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn status_popup() {
+    let mut app = app();
+    app.wifi_credentials.status = Status::new(
+        "Successfully connected to 'Home'".to_string(),
+        ExitStatus::from_raw(0),
+    );
+    app.wifi_credentials.flags.show_status_popup = true;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn failure_popup() {
+    let mut app = app();
+    app.wifi_credentials.status = Status::new(
+        "Error: Connection activation failed: Secrets were required, but not provided.".to_string(),
+        ExitStatus::from_raw(4 << 8),
+    );
+    app.wifi_credentials.flags.show_status_popup = true;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn scrolled_network_list() {
+    let mut app = app();
+    let networks: Vec<WifiNetwork> = (0..40)
+        .map(|i| network(&format!("Network {:02}", i), 90 - i, "WPA2", false, false))
+        .collect();
+    app.wifi_list = Arc::new(RwLock::new(networks));
+    app.selected = 30;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn terminal_too_small() {
+    let app = app();
+    assert_snapshot!(render_sized(&app, 30, 8));
+}
+
+#[test]
+fn password_popup_on_short_terminal() {
+    let mut app = app();
+    app.wifi_credentials.flags.show_password_popup = true;
+    app.wifi_credentials.ssid = "Neighbour 5G".to_string();
+    app.wifi_credentials.password = "a long passphrase that does not fit".to_string();
+    app.wifi_credentials.cursor_pos = 35;
+    assert_snapshot!(render_sized(&app, 44, 12));
+    // the cursor sits after the last character, on the input line of the popup
+    assert_eq!(app.cursor.get(), Some(Position::new(38, 5)));
+}
+
+#[test]
+fn status_bar() {
+    let app = app();
+    *app.connection_info.write().unwrap() = ConnectionInfo {
+        device: Some("wlan0".to_string()),
+        connection: Some("Home".to_string()),
+        ip_address: Some("192.168.1.5/24".to_string()),
+        connectivity: Connectivity::Full,
+    };
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn toasts() {
+    let mut app = app();
+    app.report(Status::new(
+        "Successfully connected to 'Home'".to_string(),
+        ExitStatus::from_raw(0),
+    ));
+    app.report(Status::new(
+        "Error: not connected to any network.\nsecond line".to_string(),
+        ExitStatus::from_raw(1 << 8),
+    ));
+    assert!(!app.wifi_credentials.flags.show_status_popup);
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn notification_history() {
+    let mut app = app();
+    app.notifications
+        .push(ToastKind::Info, "wlan0 is now connected".to_string());
+    app.report(Status::new(
+        "Wi-Fi radio turned on".to_string(),
+        ExitStatus::from_raw(0),
+    ));
+    app.flags.show_notifications = true;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn captive_portal_popup() {
+    let mut app = app();
+    app.connection_info.write().unwrap().connectivity = Connectivity::Portal;
+    let mut status = Status::new(
+        "Successfully connected to 'Airport'\nOpen http://nmcheck.gnome.org/ to get to the sign-in page."
+            .to_string(),
+        ExitStatus::from_raw(0),
+    );
+    status.failure = Some(FailureKind::CaptivePortal);
+    app.report(status);
+    assert!(app.wifi_credentials.flags.show_status_popup);
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn diagnostics() {
+    let mut app = app();
+    {
+        let mut diagnostics = app.diagnostics.write().unwrap();
+        diagnostics.results = vec![
+            CheckResult {
+                check: Check::Link,
+                outcome: Outcome::Pass,
+                details: "wlan0 is up".to_string(),
+            },
+            CheckResult {
+                check: Check::Address,
+                outcome: Outcome::Pass,
+                details: "192.168.1.5/24 on wlan0".to_string(),
+            },
+            CheckResult {
+                check: Check::DefaultRoute,
+                outcome: Outcome::Pass,
+                details: "via 192.168.1.1 dev wlan0".to_string(),
+            },
+            CheckResult {
+                check: Check::Gateway,
+                outcome: Outcome::Fail,
+                details: "`ping` failed: 1 packets transmitted, 0 received".to_string(),
+            },
+            CheckResult {
+                check: Check::Dns,
+                outcome: Outcome::Pass,
+                details: "example.com is 93.184.215.14".to_string(),
+            },
+        ];
+        // the last check is still running
+        diagnostics.running = true;
+    }
+    app.flags.show_diagnostics = true;
+    assert_snapshot!(render(&app));
+}
+
+#[test]
+fn marked_saved_connections() {
+    let mut app = app();
+    app.saved_connection.connections = ["Home", "Hotel Lobby", "Airport Free", "Office"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| Connections {
+            name: name.to_string(),
+            ssid: name.to_string(),
+            uuid: format!("5d1c7f36-6a39-4c5b-9c1f-7a4f0e6a111{}", i),
+            last_used: None,
+            is_hotspot: false,
+        })
+        .collect();
+    app.saved_connection.toggle_mark();
+    app.saved_connection.invert_marks();
+    assert_eq!(app.saved_connection.marked.len(), 3);
+    app.saved_connection.selected_index = 1;
+    app.flags.show_saved = true;
+    assert_snapshot!(render(&app));
+
+    app.flags.show_delete_confirmation = true;
+    assert_snapshot!("bulk_delete_confirmation", render(&app));
+}
+
+#[test]
+fn prune_preview() {
+    let mut app = app();
+    let ago = |days: u64| Some(SystemTime::now() - Duration::from_secs(days * DAY));
+    app.saved_connection.connections = vec![
+        ("Home", ago(0), false),
+        ("Hotel Lobby", ago(200), false),
+        ("Airport Free", ago(400), false),
+        ("Office", ago(40), false),
+        ("Hotspot", ago(500), true),
+        ("Printer setup", None, false),
+    ]
+    .into_iter()
+    .map(|(name, last_used, is_hotspot)| Connections {
+        name: name.to_string(),
+        ssid: name.to_string(),
+        uuid: String::new(),
+        last_used,
+        is_hotspot,
+    })
+    .collect();
+    app.flags.show_saved = true;
+    app.flags.show_prune = true;
+    assert_snapshot!(render(&app));
+
+    // a shorter period picks up the profile used 40 days ago as well
+    app.prune_days = 30;
+    let stale: Vec<&str> = app
+        .stale_connections()
+        .iter()
+        .map(|connection| connection.name.as_str())
+        .collect();
+    assert_eq!(stale, ["Hotel Lobby", "Airport Free", "Office"]);
+
+    // a period too long to be counted in seconds keeps every profile
+    app.prune_days = (1 << 32) + 1;
+    assert!(app.stale_connections().is_empty());
+    app.prune_days = u64::MAX;
+    assert!(app.stale_connections().is_empty());
+}
+
+#[test]
+fn clone_profile_popup() {
+    let mut app = app();
+    app.saved_connection.connections = ["Guest", "Guest 2"]
+        .iter()
+        .map(|name| Connections {
+            name: name.to_string(),
+            ssid: name.to_string(),
+            uuid: String::new(),
+            last_used: None,
+            is_hotspot: false,
+        })
+        .collect();
+    app.flags.show_saved = true;
+    app.open_profile_name_popup(ProfileEdit::Clone);
+    assert_eq!(app.saved_connection.name_input, "Guest 3");
+    assert_snapshot!(render(&app));
+}
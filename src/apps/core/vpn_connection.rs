@@ -0,0 +1,432 @@
+use super::App;
+use crate::apps::handlers::utils::{delete_char, enter_char, move_cursor_right};
+use crate::error::Result;
+use crate::utils::command::run_checked;
+use crate::utils::terse::records;
+use crate::utils::vpn::{activate_vpn, deactivate_vpn, import_vpn, needs_secrets};
+
+use crossterm::event::poll;
+use crossterm::event::{self, Event, KeyEvent};
+use std::process::Command;
+use std::{io, time::Duration};
+
+#[derive(Debug)]
+pub struct VpnConnection {
+    pub name: String,
+    pub uuid: String,
+    // either "vpn" (OpenVPN and other plugins) or "wireguard"
+    pub vpn_type: String,
+    // activation state reported by nmcli, empty when the connection is down
+    pub state: String,
+}
+
+impl VpnConnection {
+    pub fn is_active(&self) -> bool {
+        !self.state.is_empty()
+    }
+
+    // the key nmcli expects in the passwd-file for this kind of connection
+    fn secret_key(&self) -> &'static str {
+        if self.vpn_type == "wireguard" {
+            "wireguard.private-key"
+        } else {
+            "vpn.secrets.password"
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct VpnConnections {
+    pub connections: Vec<VpnConnection>,
+    pub selected_index: usize,
+    // secret typed into the popup shown when the activation needs one
+    pub secret: String,
+    // path typed into the import popup, kept between imports so a typo is easy to fix
+    pub import_path: String,
+    // cursor of whichever of the secret or import popups is open
+    pub cursor_pos: u16,
+}
+
+impl VpnConnections {
+    // nmcli -t -f UUID,TYPE,STATE,NAME connection show
+    pub fn fetch_vpn_connections(&mut self) -> Result<()> {
+        let output = run_checked(Command::new("nmcli").args([
+            "-t",
+            "-f",
+            "UUID,TYPE,STATE,NAME",
+            "connection",
+            "show",
+        ]))?;
+
+        let mut connections: Vec<VpnConnection> = Vec::new();
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        for [uuid, vpn_type, state, name] in records::<4>(&stdout)? {
+            if !uuid.is_empty() && (vpn_type == "vpn" || vpn_type == "wireguard") {
+                connections.push(VpnConnection {
+                    name,
+                    uuid,
+                    vpn_type,
+                    state,
+                });
+            }
+        }
+
+        // keep the selection in bounds when a connection disappears between refreshes
+        if self.selected_index >= connections.len() {
+            self.selected_index = connections.len().saturating_sub(1);
+        }
+        self.connections = connections;
+        Ok(())
+    }
+
+    pub fn selected(&self) -> Option<&VpnConnection> {
+        self.connections.get(self.selected_index)
+    }
+}
+
+impl App {
+    /// Handle keyboard input while the VPN connections view is active.
+    ///
+    /// - 'q' or Esc: close the VPN view
+    /// - Ctrl+C: exit the application
+    /// - Enter or 'o': bring the selected connection up, or down if it is already active
+    /// - 'j'/'k' or Down/Up: move the selection
+    /// - 'i': import a WireGuard or OpenVPN config file
+    /// - 'h' or '?': show the help view
+    /// - Ctrl+R: refresh the list of VPN connections
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut app = App::default();
+    /// app.open_vpn_list();
+    /// let _ = app.handle_vpn();
+    /// ```
+    pub fn handle_vpn(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('q'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.close_vpn_list();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.close_vpn_list();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('c'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.exit();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Enter,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.toggle_vpn();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('o'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.toggle_vpn();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('j'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.update_selected_vpn(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Down,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.update_selected_vpn(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('k'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.update_selected_vpn(-1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Up,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.update_selected_vpn(-1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('i'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.open_vpn_import_popup();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('h'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_help = true;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('?'),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_help = true;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char('r'),
+                    kind: event::KeyEventKind::Press,
+                    modifiers: event::KeyModifiers::CONTROL,
+                    ..
+                }) => {
+                    self.refresh_vpn_connections();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+
+    /// Handle keyboard input for the popup asking for the secret of a VPN connection.
+    ///
+    /// Enter retries the activation with the typed secret, Esc cancels it.
+    pub fn handle_vpn_secret_input(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            let vpn = &mut self.vpn_connection;
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Left,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    vpn.cursor_pos = vpn.cursor_pos.saturating_sub(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Right,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    move_cursor_right(&vpn.secret, &mut vpn.cursor_pos);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.close_vpn_secret_popup();
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char(c),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    enter_char(&mut vpn.secret, c, &vpn.cursor_pos);
+                    move_cursor_right(&vpn.secret, &mut vpn.cursor_pos);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Backspace,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    delete_char(&mut vpn.secret, &mut vpn.cursor_pos);
+                    vpn.cursor_pos = vpn.cursor_pos.saturating_sub(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Enter,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !vpn.secret.is_empty() => {
+                    if let Some(connection) = vpn.selected() {
+                        let status = activate_vpn(
+                            &connection.uuid,
+                            Some((connection.secret_key(), &vpn.secret)),
+                        );
+                        // a rejected secret is asked for again instead of ending in the status popup
+                        if needs_secrets(&status) {
+                            vpn.secret.clear();
+                            vpn.cursor_pos = 0;
+                            return Ok(());
+                        }
+                        self.report(status);
+                    }
+                    self.close_vpn_secret_popup();
+                    self.refresh_vpn_connections();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+
+    /// Handle keyboard input for the popup asking for the path of a config file to import.
+    ///
+    /// Enter imports the file and reports the created profile, or why it could not be
+    /// imported, in the status popup. Esc cancels the import.
+    pub fn handle_vpn_import_input(&mut self) -> io::Result<()> {
+        if poll(Duration::from_micros(1))? {
+            let vpn = &mut self.vpn_connection;
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Left,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    vpn.cursor_pos = vpn.cursor_pos.saturating_sub(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Right,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    move_cursor_right(&vpn.import_path, &mut vpn.cursor_pos);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Esc,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.flags.show_vpn_import_popup = false;
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Char(c),
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    enter_char(&mut vpn.import_path, c, &vpn.cursor_pos);
+                    move_cursor_right(&vpn.import_path, &mut vpn.cursor_pos);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Backspace,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) => {
+                    delete_char(&mut vpn.import_path, &mut vpn.cursor_pos);
+                    vpn.cursor_pos = vpn.cursor_pos.saturating_sub(1);
+                }
+                Event::Key(KeyEvent {
+                    code: event::KeyCode::Enter,
+                    kind: event::KeyEventKind::Press,
+                    ..
+                }) if !vpn.import_path.trim().is_empty() => {
+                    let status = import_vpn(&vpn.import_path);
+                    // the path is only forgotten once it was imported successfully
+                    if status.status_code.success() {
+                        vpn.import_path.clear();
+                    }
+                    self.report(status);
+                    self.flags.show_vpn_import_popup = false;
+                    self.refresh_vpn_connections();
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+
+    /// Bring the selected VPN connection up, or down when it is already active.
+    ///
+    /// If nmcli reports that the activation needs secrets, the secret popup is shown instead of
+    /// the status popup so that the activation can be retried with them.
+    fn toggle_vpn(&mut self) {
+        let Some(connection) = self.vpn_connection.selected() else {
+            return;
+        };
+
+        if connection.is_active() {
+            let status = deactivate_vpn(&connection.uuid);
+            self.report(status);
+        } else {
+            let status = activate_vpn(&connection.uuid, None);
+            if needs_secrets(&status) {
+                self.vpn_connection.secret.clear();
+                self.vpn_connection.cursor_pos = 0;
+                self.flags.show_vpn_secret_popup = true;
+            } else {
+                self.report(status);
+            }
+        }
+        self.refresh_vpn_connections();
+    }
+
+    /// Move the selected VPN connection by `direction`, wrapping around the list bounds.
+    pub fn update_selected_vpn(&mut self, direction: isize) {
+        let len = self.vpn_connection.connections.len();
+        if len > 0 {
+            self.vpn_connection.selected_index = ((self.vpn_connection.selected_index as isize
+                + direction)
+                .rem_euclid(len as isize))
+                as usize;
+        }
+    }
+
+    /// Re-read the VPN connections, showing the error popup when nmcli fails.
+    fn refresh_vpn_connections(&mut self) {
+        if let Err(e) = self.vpn_connection.fetch_vpn_connections() {
+            self.show_error(e);
+        }
+    }
+
+    /// Refresh the VPN connections and show the VPN view.
+    pub fn open_vpn_list(&mut self) {
+        self.refresh_vpn_connections();
+        self.flags.show_vpn = true;
+    }
+
+    /// Hide the VPN view.
+    pub fn close_vpn_list(&mut self) {
+        self.flags.show_vpn = false;
+    }
+
+    /// Show the import popup with the cursor at the end of the previously typed path.
+    pub fn open_vpn_import_popup(&mut self) {
+        self.vpn_connection.cursor_pos = self.vpn_connection.import_path.chars().count() as u16;
+        self.flags.show_vpn_import_popup = true;
+    }
+
+    /// Hide the VPN secret popup and forget whatever was typed into it.
+    pub fn close_vpn_secret_popup(&mut self) {
+        self.flags.show_vpn_secret_popup = false;
+        self.vpn_connection.secret.clear();
+        self.vpn_connection.cursor_pos = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wireguard_and_plugin_vpns_ask_for_different_secrets() {
+        let connection = |vpn_type: &str| VpnConnection {
+            name: "Office".to_string(),
+            uuid: String::new(),
+            vpn_type: vpn_type.to_string(),
+            state: String::new(),
+        };
+        assert_eq!(
+            connection("wireguard").secret_key(),
+            "wireguard.private-key"
+        );
+        assert_eq!(connection("vpn").secret_key(), "vpn.secrets.password");
+        assert!(!connection("vpn").is_active());
+    }
+}
@@ -1,13 +1,27 @@
 use super::App;
+use super::channels::channel_usage;
+use super::hotspot::HotspotField;
+use super::layout;
+use super::notifications::{Toast, ToastKind};
+use super::profile_name::ProfileEdit;
+use crate::apps::handlers::mouse::{Button, TableRows};
+use crate::utils::age::relative_age;
+use crate::utils::connection_info::Connectivity;
+use crate::utils::diagnostics::{Check, Outcome};
+use crate::utils::hotspot::wifi_qr_code;
 
 use ratatui::widgets::Clear;
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Layout, Rect},
     style::Stylize,
-    text::Line,
-    widgets::{Block, Paragraph, Row, Table, TableState, Widget},
+    text::{Line, Span},
+    widgets::{
+        Bar, BarChart, BarGroup, Block, Cell, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Sparkline, Table, Widget, Wrap,
+    },
 };
+use std::sync::PoisonError;
 use std::sync::atomic::Ordering;
 use std::time::{self, SystemTime};
 
@@ -16,19 +30,35 @@ const INFO_TEXT: [&str; 2] = [
     "(Enter) connect to network | (↑) move up | (↓) move down",
 ];
 
-const HELP_TEXT: [&str; 12] = [
+const HELP_TEXT: [&str; 28] = [
     "[Esc] quit",
     "(Ctrl+c) force quit",
     "(Ctrl+R) scan for networks",
-    "(Enter) connect to network",
+    "(Enter) connect to network or saved profile",
     "(o) connect to network",
     "(d) delete saved network",
+    "(space|a|i) select one, all or the others in the saved list",
+    "(u) undo the last deletion",
+    "(p) prune saved networks unused for a while",
+    "(r|c) rename or clone a saved profile",
     "(↑|k) move up",
     "(↓|j) move down",
+    "(PgUp|PgDn) move by a page, (Home|g) first, (End|G) last",
     "(h) help",
     "(?) help",
     "(s) view saved networks",
     "(x) disconnect from current network",
+    "(t) show the signal history of the selected network",
+    "(c) show how crowded each channel is",
+    "(D) run the connectivity diagnostics",
+    "(i) select the wireless interface",
+    "(a) create or stop a wifi hotspot",
+    "(w) turn the wifi radio on/off",
+    "(n) turn networking on/off",
+    "(v) manage vpn connections",
+    "(m) show the notification history",
+    "(i) import vpn config (in the vpn view)",
+    "mouse: click to select, double-click to connect, wheel to move",
 ];
 
 impl Widget for &App {
@@ -49,16 +79,66 @@ impl Widget for &App {
     /// app.render(Rect::new(0, 0, 80, 24), &mut buf);
     /// ```
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from("NMTUI").bold().italic().centered();
+        self.mouse.clear();
+        self.cursor.set(None);
+
+        if layout::too_small(area) {
+            render_too_small(area, buf);
+            return;
+        }
+
+        // the interface every action is pinned to is shown next to the name
+        // along with its state once nmcli monitor reported one
+        let title = match &self.ifname {
+            Some(ifname) => match self.monitor.device_states.get(ifname) {
+                Some(state) => Line::from(format!("NMTUI [{}: {}]", ifname, state)),
+                None => Line::from(format!("NMTUI [{}]", ifname)),
+            },
+            None => Line::from("NMTUI"),
+        }
+        .bold()
+        .italic()
+        .centered();
+
+        let radio_on = self.radio.wifi_enabled && self.radio.networking_enabled;
+
+        // the state of the radio is shown on the top right and turns the border red when it is off
+        let on_off = |enabled: bool| {
+            if enabled {
+                Span::from("on").green().bold()
+            } else {
+                Span::from("off").red().bold()
+            }
+        };
+        let active_connection = match &self.monitor.active_connection {
+            Some(connection) => format!(" Active: {} |", connection),
+            None => String::new(),
+        };
+        let radio_state = Line::from(vec![
+            active_connection.into(),
+            " Wi-Fi: ".into(),
+            on_off(self.radio.wifi_enabled),
+            " | Networking: ".into(),
+            on_off(self.radio.networking_enabled),
+            " ".into(),
+        ])
+        .right_aligned();
+
+        let border_color = if radio_on {
+            ratatui::style::Color::Blue
+        } else {
+            ratatui::style::Color::Red
+        };
 
         let block = Block::default()
             .borders(ratatui::widgets::Borders::ALL)
-            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
+            .border_style(ratatui::style::Style::default().fg(border_color))
             .border_type(ratatui::widgets::BorderType::Rounded)
             .title(title)
+            .title(radio_state)
             .title_bottom(Line::from(INFO_TEXT.join(" ")).italic().centered());
 
-        let header = Row::new(vec!["SSID", "SECURITY", "SAVED"]).style(
+        let header = Row::new(vec!["SSID", "SIGNAL", "SECURITY", "SAVED"]).style(
             ratatui::style::Style::default()
                 .fg(ratatui::style::Color::Yellow)
                 .bold(),
@@ -66,7 +146,21 @@ impl Widget for &App {
 
         let mut rows = Vec::new();
 
-        if self.flags.is_scanning.load(Ordering::SeqCst) {
+        if !radio_on {
+            // a soft-blocked radio scans to an empty list, so say why instead of showing nothing
+            let message = if !self.radio.networking_enabled {
+                "Networking is disabled - press (n) to enable it"
+            } else {
+                "Wi-Fi radio is disabled - press (w) to enable it"
+            };
+            rows.push(
+                Row::new(vec![message.to_string(), "".into(), "".into(), "".into()]).style(
+                    ratatui::style::Style::default()
+                        .fg(ratatui::style::Color::Red)
+                        .bold(),
+                ),
+            );
+        } else if self.flags.is_scanning.load(Ordering::SeqCst) {
             let spinner = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
             // Use some time-based value for the index
@@ -76,7 +170,7 @@ impl Widget for &App {
             // this will create the illusion of a spinner animation
             let index = SystemTime::now()
                 .duration_since(time::UNIX_EPOCH)
-                .expect("Time went backwards")
+                .unwrap_or_default()
                 .as_millis()
                 / 100;
 
@@ -86,10 +180,14 @@ impl Widget for &App {
                 format!("{} Scanning...", spinner_char),
                 "".into(),
                 "".into(),
+                "".into(),
             ]));
         } else {
-            // This will not panic untill the thread holding the write lock panics so we can just unwrap here
-            let wifi_list = self.wifi_list.read().expect("WifiNetworks lock poisoned");
+            // the list is replaced as a whole by the scans, so it is still usable after a panic elsewhere
+            let wifi_list = self
+                .wifi_list
+                .read()
+                .unwrap_or_else(PoisonError::into_inner);
 
             // populate the rows of the table that displays the wifi networks and highlights the selected one
             // This will never be empty because we always add the "Connect to Hidden network" entry
@@ -102,8 +200,15 @@ impl Widget for &App {
                 } else {
                     network.ssid.clone()
                 };
+                // the hidden network entry has no access point behind it
+                let signal = if network.bssid.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}%", network.signal)
+                };
                 let mut row = Row::new(vec![
                     ssid,
+                    signal,
                     network.security.clone(),
                     network.is_saved.to_string(),
                 ]);
@@ -119,7 +224,8 @@ impl Widget for &App {
         }
 
         let widths = [
-            Constraint::Percentage(60),
+            Constraint::Percentage(50),
+            Constraint::Percentage(10),
             Constraint::Percentage(20),
             Constraint::Percentage(20),
         ];
@@ -129,29 +235,141 @@ impl Widget for &App {
             .block(block)
             .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
 
-        let mut table_state = TableState::default();
-        table_state.select(Some(self.selected));
+        // the status bar takes the last line, the popups are still drawn over the whole screen
+        let [main_area, status_bar_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        self.render_status_bar(status_bar_area, buf);
+
+        // the signal panel takes the right side of the screen when it is open
+        let (table_area, signal_area) = if self.flags.show_signal_panel {
+            let [table_area, signal_area] =
+                Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                    .areas(main_area);
+            (table_area, Some(signal_area))
+        } else {
+            (main_area, None)
+        };
+
+        // the list is scrolled so that the selected network stays visible
+        let list_shown = radio_on && !self.flags.is_scanning.load(Ordering::SeqCst);
+        let mut table_state = self.network_table_state.borrow_mut();
+        table_state.select(list_shown.then_some(self.selected));
+        ratatui::widgets::StatefulWidget::render(table, table_area, buf, &mut table_state);
+        if list_shown {
+            let len = self.wifi_list.read().map(|list| list.len()).unwrap_or(0);
+            render_scrollbar(table_area, len, table_state.offset(), buf);
+            self.mouse.network_rows.set(TableRows {
+                area: table_rows_area(table_area),
+                offset: table_state.offset(),
+            });
+        }
+
+        // handle the render of the signal history of the selected network
+        if let Some(signal_area) = signal_area {
+            let signal_block = Block::default()
+                .title("Signal History")
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
+                .title_bottom(Line::from("(t) close").centered());
+            let inner = signal_block.inner(signal_area);
+            signal_block.render(signal_area, buf);
+
+            let [details_area, sparkline_area] =
+                Layout::vertical([Constraint::Length(4), Constraint::Min(0)]).areas(inner);
+
+            let wifi_list = self
+                .wifi_list
+                .read()
+                .unwrap_or_else(PoisonError::into_inner);
+            let history = self
+                .signal_history
+                .read()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            match wifi_list
+                .get(self.selected)
+                .filter(|network| !network.bssid.is_empty())
+            {
+                Some(network) => {
+                    let samples = history.get(&network.bssid);
+                    let (min, max) = samples
+                        .and_then(|samples| Some((*samples.iter().min()?, *samples.iter().max()?)))
+                        .unwrap_or((network.signal, network.signal));
+                    Paragraph::new(vec![
+                        Line::from(network.ssid.as_str()).bold(),
+                        Line::from(network.bssid.as_str()).italic(),
+                        Line::from(format!(
+                            "now {}% | min {}% | max {}%",
+                            network.signal, min, max
+                        )),
+                    ])
+                    .render(details_area, buf);
+
+                    // only the most recent readings that fit into the panel are drawn
+                    let data: Vec<u64> = samples
+                        .map(|samples| {
+                            let skip = samples.len().saturating_sub(sparkline_area.width as usize);
+                            samples.iter().skip(skip).map(|&s| s as u64).collect()
+                        })
+                        .unwrap_or_default();
+                    Sparkline::default()
+                        .data(&data)
+                        .max(100)
+                        .style(ratatui::style::Style::default().fg(ratatui::style::Color::Green))
+                        .render(sparkline_area, buf);
+                }
+                None => {
+                    Paragraph::new("Select a network to see its signal over time")
+                        .render(details_area, buf);
+                }
+            }
+        }
 
-        table.render(area, buf);
         // handle the render of the saved connections list
         if self.flags.show_saved {
             Clear.render(area, buf);
+            let marked = &self.saved_connection.marked;
+            let title = if marked.is_empty() {
+                "Saved Connections".to_string()
+            } else {
+                format!("Saved Connections ({} selected)", marked.len())
+            };
             let saved_block = Block::default()
-                .title("Saved Connections")
+                .title(title)
                 .borders(ratatui::widgets::Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Rounded)
-                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta));
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom(
+                    // marking with space, a and i is listed in the help, the hints would not fit
+                    Line::from("(Enter) connect | (d) delete | (p) prune").centered(),
+                );
 
-            let saved_area = Rect {
-                x: area.x + area.width / 6,
-                y: area.y + area.height / 6,
-                width: area.width * 2 / 3,
-                height: area.height * 2 / 3,
-            };
+            let saved_area = layout::popup(area, 66, 66, layout::MIN_WIDTH, layout::MIN_HEIGHT - 2);
 
+            let now = SystemTime::now();
             let mut saved_rows = Vec::new();
             for (i, connection) in self.saved_connection.connections.iter().enumerate() {
-                let mut row = Row::new(vec![connection.ssid.clone(), connection.last_used.clone()]);
+                let mut name = if connection.is_hotspot {
+                    format!("{} [hotspot]", connection.name)
+                } else {
+                    connection.name.clone()
+                };
+                // the check boxes are only shown once something is marked
+                if !marked.is_empty() {
+                    let check_box = if marked.contains(&connection.uuid) {
+                        "[x]"
+                    } else {
+                        "[ ]"
+                    };
+                    name = format!("{} {}", check_box, name);
+                }
+                let last_used = relative_age(connection.last_used, now);
+                let in_range = match self.saved_network_signal(&connection.ssid) {
+                    Some(signal) => Cell::from(format!("{}%", signal)),
+                    None => Cell::from("no").fg(ratatui::style::Color::DarkGray),
+                };
+                let mut row = Row::new(vec![Cell::from(name), in_range, Cell::from(last_used)]);
                 if i == self.saved_connection.selected_index {
                     row = row.style(
                         ratatui::style::Style::default()
@@ -162,7 +380,7 @@ impl Widget for &App {
                 saved_rows.push(row);
             }
 
-            let saved_header = Row::new(vec!["SSID", "LAST USED"]).style(
+            let saved_header = Row::new(vec!["NAME", "IN RANGE", "LAST USED"]).style(
                 ratatui::style::Style::default()
                     .fg(ratatui::style::Color::Yellow)
                     .bold(),
@@ -170,16 +388,412 @@ impl Widget for &App {
 
             let saved_table = Table::new(
                 saved_rows,
-                [Constraint::Percentage(70), Constraint::Percentage(30)],
+                [
+                    Constraint::Fill(1),
+                    Constraint::Length(8),
+                    Constraint::Length(14),
+                ],
             )
             .header(saved_header)
             .block(saved_block)
             .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
 
-            let mut saved_table_state = TableState::default();
+            let mut saved_table_state = self.saved_connection.table_state.borrow_mut();
             saved_table_state.select(Some(self.saved_connection.selected_index));
 
-            saved_table.render(saved_area, buf);
+            ratatui::widgets::StatefulWidget::render(
+                saved_table,
+                saved_area,
+                buf,
+                &mut saved_table_state,
+            );
+            render_scrollbar(
+                saved_area,
+                self.saved_connection.connections.len(),
+                saved_table_state.offset(),
+                buf,
+            );
+            self.mouse.saved_rows.set(TableRows {
+                area: table_rows_area(saved_area),
+                offset: saved_table_state.offset(),
+            });
+        }
+
+        // handle the render of the prune preview, over the saved connections list
+        if self.flags.show_prune {
+            Clear.render(area, buf);
+            let prune_block = Block::default()
+                .title(format!("Unused for more than {} days", self.prune_days))
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom(Line::from("(←|→) period | (Enter) select | (q) close").centered());
+
+            let prune_area = layout::popup(area, 66, 66, layout::MIN_WIDTH, layout::MIN_HEIGHT - 2);
+
+            let now = SystemTime::now();
+            let stale = self.stale_connections();
+            let lines: Vec<Line> = if stale.is_empty() {
+                vec![
+                    Line::from("Every saved network was used recently.")
+                        .fg(ratatui::style::Color::DarkGray),
+                ]
+            } else {
+                stale
+                    .iter()
+                    .map(|connection| {
+                        Line::from(vec![
+                            Span::from(connection.name.clone()).bold(),
+                            Span::from(format!("  {}", relative_age(connection.last_used, now)))
+                                .fg(ratatui::style::Color::DarkGray),
+                        ])
+                    })
+                    .collect()
+            };
+
+            Paragraph::new(lines)
+                .block(prune_block)
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
+                .render(prune_area, buf);
+        }
+
+        // handle the render of the vpn connections list
+        if self.flags.show_vpn {
+            Clear.render(area, buf);
+            let vpn_block = Block::default()
+                .title("VPN Connections")
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom("(Enter) up/down | (i) import | (Ctrl+R) refresh | (q) close");
+
+            let vpn_area = layout::popup(area, 66, 66, layout::MIN_WIDTH, layout::MIN_HEIGHT - 2);
+
+            let mut vpn_rows = Vec::new();
+            for (i, connection) in self.vpn_connection.connections.iter().enumerate() {
+                let state = if connection.is_active() {
+                    connection.state.clone()
+                } else {
+                    "inactive".to_string()
+                };
+                let mut row = Row::new(vec![
+                    connection.name.clone(),
+                    connection.vpn_type.clone(),
+                    state,
+                ]);
+                if i == self.vpn_connection.selected_index {
+                    row = row.style(
+                        ratatui::style::Style::default()
+                            .fg(ratatui::style::Color::Black)
+                            .bg(ratatui::style::Color::White),
+                    );
+                }
+                vpn_rows.push(row);
+            }
+
+            let vpn_header = Row::new(vec!["NAME", "TYPE", "STATE"]).style(
+                ratatui::style::Style::default()
+                    .fg(ratatui::style::Color::Yellow)
+                    .bold(),
+            );
+
+            let vpn_table = Table::new(
+                vpn_rows,
+                [
+                    Constraint::Percentage(50),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(25),
+                ],
+            )
+            .header(vpn_header)
+            .block(vpn_block)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
+
+            vpn_table.render(vpn_area, buf);
+        }
+
+        // handle the render of the channel congestion view
+        if self.flags.show_channels {
+            Clear.render(area, buf);
+            let channels_block = Block::default()
+                .title("Channel Congestion")
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom(
+                    "bar height: access points | colour: strongest signal | (Ctrl+R) rescan | (q) close",
+                );
+
+            let channels_area =
+                layout::popup(area, 84, 84, layout::MIN_WIDTH, layout::MIN_HEIGHT - 2);
+            let inner = channels_block.inner(channels_area);
+            channels_block.render(channels_area, buf);
+
+            let usage = {
+                let wifi_list = self
+                    .wifi_list
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner);
+                channel_usage(&wifi_list)
+            };
+
+            // every band gets an equal share of the height
+            let band_areas = Layout::vertical(
+                usage
+                    .iter()
+                    .map(|_| Constraint::Ratio(1, usage.len() as u32)),
+            )
+            .split(inner);
+
+            for (band_usage, band_area) in usage.iter().zip(band_areas.iter()) {
+                let bars: Vec<Bar> = band_usage
+                    .channels
+                    .iter()
+                    .map(|channel| {
+                        // a strong access point on the channel interferes the most
+                        let color = match channel.strongest_signal {
+                            70.. => ratatui::style::Color::Red,
+                            40..70 => ratatui::style::Color::Yellow,
+                            _ => ratatui::style::Color::Green,
+                        };
+                        Bar::default()
+                            .value(channel.access_points as u64)
+                            .label(Line::from(channel.channel.to_string()))
+                            .style(ratatui::style::Style::default().fg(color))
+                    })
+                    .collect();
+
+                BarChart::default()
+                    .block(Block::default().title(format!(
+                        "{} - {} access points",
+                        band_usage.band.label(),
+                        band_usage.access_points()
+                    )))
+                    .data(BarGroup::default().bars(&bars))
+                    .bar_width(3)
+                    .bar_gap(1)
+                    .render(*band_area, buf);
+            }
+        }
+
+        // handle the render of the interface selector
+        if self.flags.show_interfaces {
+            Clear.render(area, buf);
+            let interface_block = Block::default()
+                .title("Wireless Interfaces")
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom("(Enter) select | (Ctrl+R) refresh | (q) close");
+
+            let interface_area = layout::popup(area, 50, 50, layout::MIN_WIDTH - 4, 6);
+
+            // the first entry leaves the choice of the interface to NetworkManager
+            let entries = std::iter::once(("automatic".to_string(), String::new())).chain(
+                self.interfaces
+                    .interfaces
+                    .iter()
+                    .map(|interface| (interface.name.clone(), interface.state.clone())),
+            );
+
+            let mut interface_rows = Vec::new();
+            for (i, (name, state)) in entries.enumerate() {
+                let in_use = match &self.ifname {
+                    Some(ifname) => i > 0 && ifname == &name,
+                    None => i == 0,
+                };
+                let name = if in_use { format!("* {}", name) } else { name };
+                let mut row = Row::new(vec![name, state]);
+                if i == self.interfaces.selected_index {
+                    row = row.style(
+                        ratatui::style::Style::default()
+                            .fg(ratatui::style::Color::Black)
+                            .bg(ratatui::style::Color::White),
+                    );
+                }
+                interface_rows.push(row);
+            }
+
+            let interface_header = Row::new(vec!["DEVICE", "STATE"]).style(
+                ratatui::style::Style::default()
+                    .fg(ratatui::style::Color::Yellow)
+                    .bold(),
+            );
+
+            let interface_table = Table::new(
+                interface_rows,
+                [Constraint::Percentage(50), Constraint::Percentage(50)],
+            )
+            .header(interface_header)
+            .block(interface_block)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
+
+            interface_table.render(interface_area, buf);
+        }
+
+        // handle the render of the hotspot view
+        if self.flags.show_hotspot {
+            Clear.render(area, buf);
+            let hotspot = &self.hotspot;
+            let hint = if hotspot.running {
+                "(x) stop | (q) close"
+            } else {
+                "(Tab) next field | (←/→) band | (Ctrl+G) new password | (Enter) start | (Esc) close"
+            };
+            let hotspot_block = Block::default()
+                .title("Wi-Fi Hotspot")
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom(hint);
+
+            let hotspot_area =
+                layout::popup(area, 66, 66, layout::MIN_WIDTH, layout::MIN_HEIGHT - 2);
+            let inner = hotspot_block.inner(hotspot_area);
+            hotspot_block.render(hotspot_area, buf);
+
+            let [details_area, qr_area] =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .areas(inner);
+
+            // the focused field is highlighted while the form can be edited
+            let field = |label: &str, value: &str, field: HotspotField| {
+                let line = Line::from(format!("{:<10}{}", label, value));
+                if !hotspot.running && hotspot.focused == field {
+                    line.style(
+                        ratatui::style::Style::default()
+                            .fg(ratatui::style::Color::Black)
+                            .bg(ratatui::style::Color::White),
+                    )
+                } else {
+                    line
+                }
+            };
+            let state = if hotspot.running {
+                Span::from("running").green().bold()
+            } else {
+                Span::from("stopped").red().bold()
+            };
+            let mut details = vec![
+                Line::from(vec!["State:    ".into(), state]),
+                Line::from(""),
+                field("SSID:", &hotspot.ssid, HotspotField::Ssid),
+                field("Band:", hotspot.band.label(), HotspotField::Band),
+                field("Password:", &hotspot.password, HotspotField::Password),
+            ];
+            if !hotspot.running && !hotspot.is_valid() {
+                details.push(Line::from(""));
+                details.push(Line::from("The password needs 8 to 63 characters").yellow());
+            }
+            Paragraph::new(details)
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
+                .render(details_area, buf);
+
+            if hotspot.running
+                && let Some(qr_code) = wifi_qr_code(&hotspot.ssid, &hotspot.password)
+            {
+                Paragraph::new(qr_code)
+                    .style(
+                        ratatui::style::Style::default()
+                            .fg(ratatui::style::Color::White)
+                            .bg(ratatui::style::Color::Black),
+                    )
+                    .render(qr_area, buf);
+            }
+        }
+
+        // handle the render of the diagnostics view
+        if self.flags.show_diagnostics {
+            Clear.render(area, buf);
+            let title = match &self.netns {
+                Some(netns) => format!("Diagnostics [netns: {}]", netns),
+                None => "Diagnostics".to_string(),
+            };
+            let diagnostics_block = Block::default()
+                .title(title)
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom(Line::from("(r) run again | (q) close").centered());
+
+            let diagnostics_area =
+                layout::popup(area, 66, 66, layout::MIN_WIDTH, layout::MIN_HEIGHT - 2);
+
+            let diagnostics = self
+                .diagnostics
+                .read()
+                .unwrap_or_else(PoisonError::into_inner);
+            let mut lines = Vec::new();
+            let mut push_check = |check: Check, marker: &str, color, details: &str| {
+                lines.push(Line::from(vec![
+                    Span::from(format!("{} ", marker)).fg(color).bold(),
+                    Span::from(check.label()).bold(),
+                ]));
+                if !details.is_empty() {
+                    lines.push(Line::from(format!("  {}", details)).fg(color));
+                }
+            };
+            for result in &diagnostics.results {
+                let (marker, color) = match result.outcome {
+                    Outcome::Pass => ("✓", ratatui::style::Color::Green),
+                    Outcome::Fail => ("✗", ratatui::style::Color::Red),
+                    Outcome::Skipped => ("-", ratatui::style::Color::DarkGray),
+                };
+                push_check(result.check, marker, color, &result.details);
+            }
+            let pending = Check::ALL.iter().skip(diagnostics.results.len());
+            for (i, &check) in pending.enumerate() {
+                // the check after the last result is the one that is running
+                if diagnostics.running && i == 0 {
+                    push_check(check, "…", ratatui::style::Color::Yellow, "checking");
+                } else {
+                    push_check(check, " ", ratatui::style::Color::DarkGray, "");
+                }
+            }
+
+            Paragraph::new(lines)
+                .block(diagnostics_block)
+                .wrap(Wrap { trim: false })
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
+                .render(diagnostics_area, buf);
+        }
+
+        // handle the render of the notification history
+        if self.flags.show_notifications {
+            Clear.render(area, buf);
+            let history_block = Block::default()
+                .title("Notifications")
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom(Line::from("(m) close").centered());
+
+            let history_area =
+                layout::popup(area, 66, 66, layout::MIN_WIDTH, layout::MIN_HEIGHT - 2);
+
+            let mut lines: Vec<Line> = self
+                .notifications
+                .history()
+                .map(|toast| {
+                    let marker = match toast.kind {
+                        ToastKind::Success => "✓ ",
+                        ToastKind::Failure => "✗ ",
+                        ToastKind::Info => "• ",
+                    };
+                    Line::from(vec![
+                        Span::from(marker).fg(toast_color(toast.kind)),
+                        Span::from(toast.message.as_str()),
+                    ])
+                })
+                .collect();
+            if lines.is_empty() {
+                lines.push(Line::from("Nothing happened yet").italic());
+            }
+
+            Paragraph::new(lines)
+                .block(history_block)
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
+                .render(history_area, buf);
         }
 
         // handle the render of the help menu
@@ -191,12 +805,7 @@ impl Widget for &App {
                 .border_type(ratatui::widgets::BorderType::Rounded)
                 .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta));
 
-            let help_area = Rect {
-                x: area.x + area.width / 6,
-                y: area.y + area.height / 6,
-                width: area.width * 2 / 3,
-                height: area.height * 2 / 3,
-            };
+            let help_area = layout::popup(area, 66, 66, layout::MIN_WIDTH, layout::MIN_HEIGHT - 2);
 
             let help_paragraph = Paragraph::new(HELP_TEXT.join("\n"))
                 .block(help_block)
@@ -212,22 +821,55 @@ impl Widget for &App {
                 .title("Confirm Deletion")
                 .borders(ratatui::widgets::Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Rounded)
-                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
-                .title_bottom("Are you sure you want to delete this saved network? (y/n)");
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta));
 
-            let popup_area = Rect {
-                x: area.x + area.width / 4,
-                y: area.y + area.height / 4,
-                width: area.width / 2,
-                height: area.height / 10,
+            // the question wraps onto a second line on narrow terminals
+            let popup_area = layout::dialog(area, 2);
+
+            let marked = self.saved_connection.marked.len();
+            let question = if self.flags.show_saved && marked > 0 {
+                format!(
+                    "Delete the {} selected saved networks? This cannot be undone. (y/n)",
+                    marked
+                )
+            } else {
+                "Are you sure you want to delete this saved network? (y/n)".to_string()
             };
+            let confirmation_paragraph = Paragraph::new(question)
+                .block(popup_block)
+                .wrap(Wrap { trim: true })
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
 
-            let confirmation_paragraph =
-                Paragraph::new("Press 'y' to confirm deletion or 'n' to cancel.")
-                    .block(popup_block)
-                    .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
+            confirmation_paragraph.render(popup_area, buf);
+            self.render_buttons(popup_area, &[Button::Yes, Button::No], buf);
+        }
+
+        // handle the render of the networking confirmation popup
+        if self.flags.show_networking_confirmation {
+            Clear.render(area, buf);
+            let action = if self.radio.networking_enabled {
+                "disable"
+            } else {
+                "enable"
+            };
+            let popup_block = Block::default()
+                .title("Confirm Networking")
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta));
+
+            let popup_area = layout::dialog(area, 2);
+
+            let confirmation_paragraph = Paragraph::new(format!(
+                "Are you sure you want to {} all networking? (y/n)",
+                action
+            ))
+            .block(popup_block)
+            .wrap(Wrap { trim: true })
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
 
             confirmation_paragraph.render(popup_area, buf);
+            self.render_buttons(popup_area, &[Button::Yes, Button::No], buf);
         }
 
         // handle the render of the ssid input popup for hidden networks
@@ -239,15 +881,11 @@ impl Widget for &App {
                 .border_type(ratatui::widgets::BorderType::Rounded)
                 .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta));
 
-            let popup_area = Rect {
-                x: area.x + area.width / 4,
-                y: area.y + area.height / 4,
-                width: area.width / 2,
-                height: area.height / 10,
-            };
+            let popup_area = layout::dialog(area, 1);
 
             let ssid_paragraph = Paragraph::new(self.wifi_credentials.ssid.as_str())
                 .block(popup_block)
+                .scroll(self.input_scroll(popup_area, self.wifi_credentials.cursor_pos))
                 .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
 
             ssid_paragraph.render(popup_area, buf);
@@ -263,46 +901,317 @@ impl Widget for &App {
                 .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
                 .title_bottom("If the network is open, just press Enter");
 
-            let popup_area = Rect {
-                x: area.x + area.width / 4,
-                y: area.y + area.height / 4,
-                width: area.width / 2,
-                height: area.height / 10,
-            };
+            let popup_area = layout::dialog(area, 1);
 
             let password_paragraph = Paragraph::new(self.wifi_credentials.password.as_str())
                 .block(popup_block)
+                .scroll(self.input_scroll(popup_area, self.wifi_credentials.cursor_pos))
                 .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
 
             password_paragraph.render(popup_area, buf);
         }
 
+        // handle the render of the secret input popup for vpn connections
+        if self.flags.show_vpn_secret_popup {
+            Clear.render(area, buf);
+            let name = self
+                .vpn_connection
+                .selected()
+                .map(|connection| connection.name.as_str())
+                .unwrap_or_default();
+            let popup_block = Block::default()
+                .title(format!("Enter the secret for '{}'", name))
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom("The connection needs a password or key to come up");
+
+            let popup_area = layout::dialog(area, 1);
+
+            // the secret is masked so that it does not stay on the screen
+            let masked = "*".repeat(self.vpn_connection.secret.chars().count());
+            let secret_paragraph = Paragraph::new(masked)
+                .block(popup_block)
+                .scroll(self.input_scroll(popup_area, self.vpn_connection.cursor_pos))
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
+
+            secret_paragraph.render(popup_area, buf);
+        }
+
+        // handle the render of the path input popup for importing vpn configs
+        if self.flags.show_vpn_import_popup {
+            Clear.render(area, buf);
+            let popup_block = Block::default()
+                .title("Import a WireGuard (.conf) or OpenVPN (.ovpn) file")
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom("Enter the path of the config file");
+
+            let popup_area = layout::dialog(area, 1);
+
+            let path_paragraph = Paragraph::new(self.vpn_connection.import_path.as_str())
+                .block(popup_block)
+                .scroll(self.input_scroll(popup_area, self.vpn_connection.cursor_pos))
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
+
+            path_paragraph.render(popup_area, buf);
+        }
+
+        // handle the render of the popup asking for the name of a saved profile
+        if self.flags.show_profile_name_popup {
+            Clear.render(area, buf);
+            let saved = &self.saved_connection;
+            let name = saved
+                .connections
+                .get(saved.selected_index)
+                .map(|connection| connection.name.as_str())
+                .unwrap_or_default();
+            let title = match saved.name_edit {
+                ProfileEdit::Rename => format!("Rename '{}'", name),
+                ProfileEdit::Clone => format!("Clone '{}'", name),
+            };
+            let popup_block = Block::default()
+                .title(title)
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta))
+                .title_bottom("Enter the new profile name");
+
+            let popup_area = layout::dialog(area, 1);
+
+            let name_paragraph = Paragraph::new(saved.name_input.as_str())
+                .block(popup_block)
+                .scroll(self.input_scroll(popup_area, saved.cursor_pos))
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
+
+            name_paragraph.render(popup_area, buf);
+        }
+
         // handle the render of the status popup
         if self.wifi_credentials.flags.show_status_popup {
             Clear.render(area, buf);
+            // failed commands are explained with what to do next when the failure is understood
+            let status = &self.wifi_credentials.status;
+            let (title, color) = match status.failure {
+                None => ("Status", ratatui::style::Color::Magenta),
+                Some(failure) => (failure.title(), ratatui::style::Color::Red),
+            };
             let status_block = Block::default()
-                .title("Status")
+                .title(title)
                 .borders(ratatui::widgets::Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Rounded)
-                .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Magenta));
+                .border_style(ratatui::style::Style::default().fg(color));
 
-            let status_area = Rect {
-                x: area.x + area.width / 4,
-                y: area.y + area.height / 3,
-                width: area.width / 2,
-                height: area.height / 4,
-            };
-            let stauts = format!(
-                "{}\n{}",
-                self.wifi_credentials.status.status_code,
-                self.wifi_credentials.status.status_message
-            );
+            let status_area = layout::popup(area, 50, 50, layout::MIN_WIDTH - 4, 10);
+            let mut lines: Vec<Line> = Vec::new();
+            if let Some(explanation) = status.failure.and_then(|failure| failure.explanation()) {
+                lines.push(Line::from(explanation));
+            }
+            if let Some(suggestion) = status.failure.and_then(|failure| failure.suggestion()) {
+                lines.push(Line::styled(
+                    suggestion,
+                    ratatui::style::Style::default().fg(ratatui::style::Color::Yellow),
+                ));
+            }
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            // the exit status only helps when the failure could not be explained
+            if lines.is_empty() {
+                lines.push(Line::styled(
+                    status.status_code.to_string(),
+                    ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray),
+                ));
+            }
+            for line in status.status_message.trim_end().lines() {
+                lines.push(Line::from(line.to_string()));
+            }
 
-            let status_paragraph = Paragraph::new(stauts)
+            let status_paragraph = Paragraph::new(lines)
                 .block(status_block)
+                .wrap(Wrap { trim: true })
                 .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
 
             status_paragraph.render(status_area, buf);
+            if status
+                .failure
+                .is_some_and(|failure| failure.offers_password_retry())
+            {
+                self.render_buttons(status_area, &[Button::Retry, Button::Ok], buf);
+            } else {
+                self.render_buttons(status_area, &[Button::Ok], buf);
+            }
         }
+
+        // handle the render of the toasts, stacked above the status bar with the newest at the bottom
+        let toasts: Vec<&Toast> = self.notifications.visible().collect();
+        let mut bottom = area.bottom().saturating_sub(1);
+        for toast in toasts.iter().rev() {
+            let width = (toast.message.chars().count() as u16 + 4).min(area.width);
+            let Some(y) = bottom.checked_sub(3).filter(|&y| y >= area.y) else {
+                break;
+            };
+            let toast_area = Rect {
+                x: area.x + area.width - width,
+                y,
+                width,
+                height: 3,
+            };
+            bottom = y;
+            Clear.render(toast_area, buf);
+            let toast_block = Block::default()
+                .borders(ratatui::widgets::Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(ratatui::style::Style::default().fg(toast_color(toast.kind)));
+
+            Paragraph::new(toast.message.as_str())
+                .block(toast_block)
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
+                .render(toast_area, buf);
+        }
+    }
+}
+
+impl App {
+    /// Draw the status bar with the connected network, its interface, address and signal, and
+    /// whether NetworkManager reaches the internet through it.
+    fn render_status_bar(&self, area: Rect, buf: &mut Buffer) {
+        let info = self
+            .connection_info
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let wifi_list = self
+            .wifi_list
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let in_use = wifi_list.iter().find(|network| network.in_use);
+
+        let mut spans = vec![Span::from(" ")];
+        match &info.device {
+            Some(device) => {
+                // the profile name is only shown until a scan found the access point in use
+                let ssid = in_use
+                    .map(|network| network.ssid.as_str())
+                    .or(info.connection.as_deref())
+                    .unwrap_or_default();
+                spans.push(Span::from(ssid.to_string()).bold());
+                spans.push(Span::from(format!(" on {}", device)));
+                if let Some(ip_address) = &info.ip_address {
+                    spans.push(Span::from(format!(" | {}", ip_address)));
+                }
+                if let Some(network) = in_use {
+                    spans.push(Span::from(format!(" | {}%", network.signal)));
+                }
+            }
+            None => spans.push(Span::from("Not connected").italic()),
+        }
+
+        let connectivity_color = match info.connectivity {
+            Connectivity::Full => ratatui::style::Color::Green,
+            Connectivity::Limited | Connectivity::Portal => ratatui::style::Color::Yellow,
+            Connectivity::None => ratatui::style::Color::Red,
+            Connectivity::Unknown => ratatui::style::Color::DarkGray,
+        };
+        spans.push(Span::from(" | Internet: "));
+        spans.push(Span::from(info.connectivity.to_string()).fg(connectivity_color));
+        if info.connectivity == Connectivity::Portal {
+            spans.push(Span::from(" (sign-in required)").fg(connectivity_color));
+        }
+        if let Some(profile) = self.undoable_delete() {
+            spans.push(Span::from(format!(" | (u) undo deleting '{}'", profile.name)).yellow());
+        }
+
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+
+    /// Scroll a single line input drawn in `popup_area` so that the cursor stays visible, and
+    /// remember where the terminal cursor goes for [`App::draw`].
+    fn input_scroll(&self, popup_area: Rect, cursor_pos: u16) -> (u16, u16) {
+        self.cursor
+            .set(Some(layout::input_cursor(popup_area, cursor_pos)));
+        (
+            0,
+            layout::input_scroll(popup_area.width.saturating_sub(2), cursor_pos),
+        )
+    }
+
+    /// Draw `buttons` right aligned on the bottom border of `popup_area` and remember where they
+    /// are, so a click on them can be told apart from a click on the popup.
+    fn render_buttons(&self, popup_area: Rect, buttons: &[Button], buf: &mut Buffer) {
+        let y = popup_area.bottom().saturating_sub(1);
+        let mut right = popup_area.right().saturating_sub(2);
+        for button in buttons.iter().rev() {
+            let width = button.label().chars().count() as u16;
+            // the buttons that do not fit are left out, the keys still work
+            if right < popup_area.x + 1 + width {
+                break;
+            }
+            let button_area = Rect {
+                x: right - width,
+                y,
+                width,
+                height: 1,
+            };
+            Line::from(button.label()).bold().render(button_area, buf);
+            self.mouse.buttons.borrow_mut().push((button_area, *button));
+            right = button_area.x - 1;
+        }
+    }
+}
+
+// Draw a scrollbar over the right border of a bordered table when its rows do not fit
+fn render_scrollbar(table_area: Rect, len: usize, offset: usize, buf: &mut Buffer) {
+    let visible = table_rows_area(table_area).height as usize;
+    if len <= visible {
+        return;
+    }
+    // the thumb spans the visible rows, so the content length is the number of offsets
+    let mut state = ScrollbarState::new(len - visible)
+        .position(offset)
+        .viewport_content_length(visible);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None);
+    let scrollbar_area = Rect {
+        y: table_area.y + 2,
+        height: table_area.height.saturating_sub(3),
+        ..table_area
+    };
+    ratatui::widgets::StatefulWidget::render(scrollbar, scrollbar_area, buf, &mut state);
+}
+
+// Area of the rows of a bordered table with a one line header
+fn table_rows_area(table_area: Rect) -> Rect {
+    Rect {
+        x: table_area.x + 1,
+        y: table_area.y + 2,
+        width: table_area.width.saturating_sub(2),
+        height: table_area.height.saturating_sub(3),
+    }
+}
+
+// Shown instead of the ui when the terminal cannot fit it
+fn render_too_small(area: Rect, buf: &mut Buffer) {
+    let lines = vec![
+        Line::from("Terminal too small").bold(),
+        Line::from(format!("{}x{}", area.width, area.height)).red(),
+        Line::from(format!(
+            "needs {}x{}",
+            layout::MIN_WIDTH,
+            layout::MIN_HEIGHT
+        )),
+    ];
+    let height = lines.len() as u16;
+    Paragraph::new(lines)
+        .centered()
+        .render(layout::centered(area, area.width, height), buf);
+}
+
+fn toast_color(kind: ToastKind) -> ratatui::style::Color {
+    match kind {
+        ToastKind::Success => ratatui::style::Color::Green,
+        ToastKind::Failure => ratatui::style::Color::Red,
+        ToastKind::Info => ratatui::style::Color::Cyan,
     }
 }
@@ -1,8 +1,9 @@
 pub mod flags;
+pub mod mouse;
 mod password_handler;
 mod ssid_handler;
 pub mod status;
-mod utils;
+pub mod utils;
 use crate::utils::connect::connect_to_network;
 use flags::Flags;
 use status::Status;
@@ -11,18 +12,29 @@ use status::Status;
 pub struct WifiInputState {
     pub ssid: String,
     pub password: String,
+    // name and uuid of the saved profile connected from the saved list, a password entered again
+    // is stored in that profile instead of connecting by ssid
+    pub profile: Option<(String, String)>,
     pub cursor_pos: u16,
     pub status: Status,
     pub flags: Flags,
 }
 
 impl WifiInputState {
-    fn prepare_to_connect(&mut self) {
+    // Connect with the entered password, the result is reported by the caller
+    fn prepare_to_connect(&mut self, ifname: Option<&str>) -> Status {
         self.flags.show_password_popup = false;
-        self.status = connect_to_network(self);
+        let status = connect_to_network(self, ifname);
         self.reset_cursor_position();
-        self.flags.is_hidden = false;
-        self.flags.show_status_popup = true;
+        // a hidden network is still needed when the password is entered again from the popup
+        if !status
+            .failure
+            .is_some_and(|failure| failure.offers_password_retry())
+        {
+            self.flags.is_hidden = false;
+            self.profile = None;
+        }
+        status
     }
 
     fn move_cursor_left(&mut self) {
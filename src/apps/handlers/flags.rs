@@ -12,10 +12,22 @@ pub struct Flags {
     // this is currently use to show a loading indicator in the ui while scanning is going on
     pub is_scanning: Arc<AtomicBool>,
 
+    pub show_channels: bool,
     pub show_delete_confirmation: bool,
+    pub show_diagnostics: bool,
     pub show_help: bool,
+    pub show_hotspot: bool,
+    pub show_interfaces: bool,
+    pub show_networking_confirmation: bool,
+    pub show_notifications: bool,
     pub show_password_popup: bool,
+    pub show_profile_name_popup: bool,
+    pub show_prune: bool,
     pub show_saved: bool,
+    pub show_signal_panel: bool,
     pub show_ssid_popup: bool,
     pub show_status_popup: bool,
+    pub show_vpn: bool,
+    pub show_vpn_import_popup: bool,
+    pub show_vpn_secret_popup: bool,
 }
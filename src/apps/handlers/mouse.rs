@@ -0,0 +1,100 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+// two clicks on the same cell within this time are a double click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+// Buttons drawn on the bottom border of the popups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Button {
+    Yes,
+    No,
+    Ok,
+    Retry,
+}
+
+impl Button {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Button::Yes => "[ Yes ]",
+            Button::No => "[ No ]",
+            Button::Ok => "[ OK ]",
+            Button::Retry => "[ Retry ]",
+        }
+    }
+}
+
+// Rows of a table as drawn in the last frame
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TableRows {
+    // where the rows are drawn, without the borders and the header
+    pub area: Rect,
+    // index of the row drawn first
+    pub offset: usize,
+}
+
+impl TableRows {
+    // Index of the row under the mouse, it may be past the end of the list
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.area
+            .contains(Position::new(column, row))
+            .then(|| self.offset + (row - self.area.y) as usize)
+    }
+}
+
+// What the last frame drew where, so that mouse events can be mapped back to it
+//
+// The widget only gets a shared reference to the app while rendering, so the areas are kept in
+// cells and replaced on every frame.
+#[derive(Debug, Default)]
+pub struct Mouse {
+    pub network_rows: Cell<TableRows>,
+    pub saved_rows: Cell<TableRows>,
+    pub buttons: RefCell<Vec<(Rect, Button)>>,
+    last_click: Cell<Option<(Instant, u16, u16)>>,
+}
+
+impl Mouse {
+    // Forget the areas of the previous frame
+    pub fn clear(&self) {
+        self.network_rows.take();
+        self.saved_rows.take();
+        self.buttons.borrow_mut().clear();
+    }
+
+    pub fn button_at(&self, column: u16, row: u16) -> Option<Button> {
+        self.buttons
+            .borrow()
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|&(_, button)| button)
+    }
+
+    // The button clicked with a left click, if any
+    pub fn clicked_button(&self, event: &MouseEvent) -> Option<Button> {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.button_at(event.column, event.row),
+            _ => None,
+        }
+    }
+
+    // Record a left click and tell whether it completes a double click
+    pub fn is_double_click(&self, column: u16, row: u16) -> bool {
+        let now = Instant::now();
+        let double = self
+            .last_click
+            .get()
+            .is_some_and(|(at, last_column, last_row)| {
+                last_column == column && last_row == row && now - at <= DOUBLE_CLICK_INTERVAL
+            });
+        // a third click starts over instead of being another double click
+        self.last_click.set(if double {
+            None
+        } else {
+            Some((now, column, row))
+        });
+        double
+    }
+}
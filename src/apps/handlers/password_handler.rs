@@ -1,11 +1,13 @@
 use super::WifiInputState;
+use super::status::Status;
 use super::utils::{delete_char, enter_char, move_cursor_right};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind::Press, poll};
 use std::io;
 use std::time::Duration;
 
 impl WifiInputState {
-    pub fn handle_password_input(&mut self) -> io::Result<()> {
+    // Returns the result of the connection once the password was entered
+    pub fn handle_password_input(&mut self, ifname: Option<&str>) -> io::Result<Option<Status>> {
         if poll(Duration::from_micros(1))? {
             match event::read()? {
                 Event::Key(KeyEvent {
@@ -55,14 +57,12 @@ impl WifiInputState {
                     code: KeyCode::Enter,
                     kind: Press,
                     ..
-                }) => {
-                    if self.password.is_empty() || self.password.chars().count() >= 8 {
-                        self.prepare_to_connect();
-                    }
+                }) if self.password.is_empty() || self.password.chars().count() >= 8 => {
+                    return Ok(Some(self.prepare_to_connect(ifname)));
                 }
                 _ => {}
             };
         }
-        Ok(())
+        Ok(None)
     }
 }
@@ -1,6 +1,10 @@
 use super::WifiInputState;
+use super::mouse::{Button, Mouse};
+use crate::error::Error;
+use crate::utils::failure::FailureKind;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind::Press, poll};
 use std::io;
+use std::os::unix::process::ExitStatusExt;
 use std::process::ExitStatus;
 use std::time::Duration;
 
@@ -8,6 +12,8 @@ use std::time::Duration;
 pub struct Status {
     pub status_message: String,
     pub status_code: ExitStatus,
+    // what went wrong, None when the command succeeded
+    pub failure: Option<FailureKind>,
 }
 impl Default for Status {
     fn default() -> Self {
@@ -15,21 +21,38 @@ impl Default for Status {
         Status {
             status_message,
             status_code: ExitStatus::default(),
+            failure: None,
         }
     }
 }
 
 impl Status {
     pub fn new(status_message: String, status_code: ExitStatus) -> Self {
+        let failure =
+            (!status_code.success()).then(|| FailureKind::classify(status_code, &status_message));
         Self {
             status_message,
             status_code,
+            failure,
         }
     }
+
+    // Failures with something for the user to do are kept on the screen until dismissed
+    pub fn needs_attention(&self) -> bool {
+        self.failure
+            .is_some_and(|failure| failure.suggestion().is_some())
+    }
+}
+
+// An error is shown like a failed command, with exit status 1
+impl From<Error> for Status {
+    fn from(error: Error) -> Self {
+        Status::new(error.to_string(), ExitStatus::from_raw(1 << 8))
+    }
 }
 
 impl WifiInputState {
-    pub fn handle_status_message(&mut self) -> io::Result<()> {
+    pub fn handle_status_message(&mut self, mouse: &Mouse) -> io::Result<()> {
         if poll(Duration::from_micros(1))? {
             match event::read()? {
                 Event::Key(KeyEvent {
@@ -37,31 +60,59 @@ impl WifiInputState {
                     kind: Press,
                     ..
                 }) => {
-                    self.flags.show_status_popup = false;
-                    self.status.status_message.clear();
-                    self.status.status_code = ExitStatus::default();
+                    self.close_status_popup();
                 }
                 Event::Key(KeyEvent {
                     code: KeyCode::Char('q'),
                     kind: Press,
                     ..
                 }) => {
-                    self.flags.show_status_popup = false;
-                    self.status.status_message.clear();
-                    self.status.status_code = ExitStatus::default();
+                    self.close_status_popup();
                 }
                 Event::Key(KeyEvent {
                     code: KeyCode::Enter,
                     kind: Press,
                     ..
                 }) => {
-                    self.flags.show_status_popup = false;
-                    self.status.status_message.clear();
-                    self.status.status_code = ExitStatus::default();
+                    self.close_status_popup();
                 }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('r'),
+                    kind: Press,
+                    ..
+                }) if self
+                    .status
+                    .failure
+                    .is_some_and(|failure| failure.offers_password_retry()) =>
+                {
+                    self.retry_password();
+                }
+                Event::Mouse(event) => match mouse.clicked_button(&event) {
+                    Some(Button::Ok) => self.close_status_popup(),
+                    Some(Button::Retry) => self.retry_password(),
+                    _ => {}
+                },
                 _ => {}
             }
         };
         Ok(())
     }
+
+    fn close_status_popup(&mut self) {
+        self.flags.show_status_popup = false;
+        self.status = Status::default();
+        // kept while the popup was open so that a retry still connects to the hidden network, or
+        // updates the saved profile
+        self.flags.is_hidden = false;
+        self.profile = None;
+    }
+
+    // Ask again for the password of the network that just failed, is_hidden is left as it was
+    fn retry_password(&mut self) {
+        self.flags.show_status_popup = false;
+        self.status = Status::default();
+        self.password.clear();
+        self.reset_cursor_position();
+        self.flags.show_password_popup = true;
+    }
 }
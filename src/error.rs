@@ -0,0 +1,68 @@
+use std::fmt;
+use std::io;
+use std::process::Output;
+
+// Failures of the NetworkManager backend, shown to the user in the error popup
+#[derive(Debug)]
+pub enum Error {
+    // nmcli is not installed or not in PATH
+    NmcliMissing,
+    // nmcli runs but cannot reach the NetworkManager daemon
+    NotRunning,
+    // polkit refused the action, with the message of nmcli
+    PermissionDenied(String),
+    // nmcli printed something that could not be understood
+    Parse(String),
+    // nmcli gave up waiting, with the message of nmcli
+    Timeout(String),
+    // any other failure reported by nmcli on stderr
+    Nmcli(String),
+    Io(io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    // Classify the stderr of a failed nmcli call
+    pub fn from_output(output: &Output) -> Self {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let lowercase = stderr.to_lowercase();
+        if lowercase.contains("networkmanager is not running") {
+            Error::NotRunning
+        } else if lowercase.contains("not authorized")
+            || lowercase.contains("insufficient privileges")
+        {
+            Error::PermissionDenied(stderr)
+        } else if lowercase.contains("timeout") || lowercase.contains("timed out") {
+            Error::Timeout(stderr)
+        } else {
+            Error::Nmcli(stderr)
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NmcliMissing => write!(f, "nmcli was not found, is NetworkManager installed?"),
+            Error::NotRunning => write!(f, "NetworkManager is not running"),
+            Error::PermissionDenied(message) => write!(f, "Permission denied: {}", message),
+            Error::Parse(message) => write!(f, "Unexpected output from nmcli: {}", message),
+            Error::Timeout(message) => write!(f, "nmcli timed out: {}", message),
+            Error::Nmcli(message) => write!(f, "{}", message),
+            Error::Io(e) => write!(f, "Failed to execute nmcli: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => Error::NmcliMissing,
+            io::ErrorKind::PermissionDenied => Error::PermissionDenied(e.to_string()),
+            _ => Error::Io(e),
+        }
+    }
+}
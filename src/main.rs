@@ -1,6 +1,9 @@
 use color_eyre::eyre::Result;
 mod apps;
+mod error;
 mod utils;
+use utils::cli::parse_args;
+use utils::logger::{self, default_log_path};
 use utils::tui::tui;
 
 #[derive(Debug)]
@@ -9,6 +12,13 @@ struct WifiNetwork {
     in_use: bool,
     ssid: String,
     security: String,
+    // access point the row was reported for, the same ssid can show up once per bssid
+    bssid: String,
+    // signal strength in percent
+    signal: u8,
+    channel: u16,
+    // centre frequency in MHz
+    frequency: u32,
 }
 
 #[derive(Default, Debug)]
@@ -18,6 +28,22 @@ struct AppState {
 
 fn main() -> Result<()> {
     color_eyre::install()?;
-    tui().expect("Failed to run TUI application");
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("nmtui: {}", e);
+            std::process::exit(2);
+        }
+    };
+    if args.log_file.is_some() || args.log_level.is_some() {
+        let path = args.log_file.clone().unwrap_or_else(default_log_path);
+        let level = args.log_level.unwrap_or_default();
+        if let Err(e) = logger::init(&path, level) {
+            eprintln!("nmtui: cannot open log file {}: {}", path.display(), e);
+            std::process::exit(2);
+        }
+        logger::info(&format!("nmtui {} started", env!("CARGO_PKG_VERSION")));
+    }
+    tui(args).expect("Failed to run TUI application");
     Ok(())
 }
@@ -1,6 +1,22 @@
+pub mod age;
+pub mod cli;
+pub mod command;
 pub mod connect;
+pub mod connection_info;
 pub mod delete_connection;
+pub mod diagnostics;
 pub mod disconnect_connection;
+pub mod failure;
+pub mod hotspot;
+pub mod interface;
+pub mod logger;
+pub mod monitor;
+pub mod portal;
+pub mod profile;
+pub mod radio;
 pub mod saved_connection;
 pub mod scan;
+pub mod signal_history;
+pub mod terse;
 pub mod tui;
+pub mod vpn;
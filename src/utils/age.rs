@@ -0,0 +1,64 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+const MONTH: u64 = 30 * DAY;
+const YEAR: u64 = 365 * DAY;
+
+// A TIMESTAMP field of nmcli, seconds since the epoch with 0 for a profile that was never used
+pub fn parse_timestamp(value: &str) -> Option<SystemTime> {
+    match value.trim().parse::<u64>() {
+        Ok(0) | Err(_) => None,
+        Ok(seconds) => Some(UNIX_EPOCH + Duration::from_secs(seconds)),
+    }
+}
+
+// How long ago `time` was, like "3 months ago", or "never"
+pub fn relative_age(time: Option<SystemTime>, now: SystemTime) -> String {
+    let Some(time) = time else {
+        return "never".to_string();
+    };
+    // a clock that went backwards counts as just now
+    let seconds = now.duration_since(time).unwrap_or_default().as_secs();
+    let (count, unit) = match seconds {
+        s if s < MINUTE => return "just now".to_string(),
+        s if s < HOUR => (s / MINUTE, "minute"),
+        s if s < DAY => (s / HOUR, "hour"),
+        s if s < WEEK => (s / DAY, "day"),
+        s if s < MONTH => (s / WEEK, "week"),
+        s if s < YEAR => (s / MONTH, "month"),
+        s => (s / YEAR, "year"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{} ago", count, unit, plural)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_timestamp_means_never_used() {
+        assert_eq!(parse_timestamp("0"), None);
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(
+            parse_timestamp("1718000000"),
+            Some(UNIX_EPOCH + Duration::from_secs(1_718_000_000))
+        );
+    }
+
+    #[test]
+    fn ages_are_rounded_down_to_the_largest_unit() {
+        let now = UNIX_EPOCH + Duration::from_secs(2_000_000_000);
+        let ago = |seconds: u64| relative_age(Some(now - Duration::from_secs(seconds)), now);
+        assert_eq!(ago(5), "just now");
+        assert_eq!(ago(MINUTE), "1 minute ago");
+        assert_eq!(ago(3 * HOUR + 59 * MINUTE), "3 hours ago");
+        assert_eq!(ago(2 * WEEK), "2 weeks ago");
+        assert_eq!(ago(100 * DAY), "3 months ago");
+        assert_eq!(ago(2 * YEAR), "2 years ago");
+        assert_eq!(relative_age(None, now), "never");
+    }
+}
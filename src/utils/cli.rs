@@ -0,0 +1,132 @@
+use crate::utils::logger::LogLevel;
+use std::env;
+use std::path::PathBuf;
+
+const USAGE: &str = "Usage: nmtui [OPTIONS]
+
+Options:
+  -i, --ifname <DEVICE>  wireless interface to scan, connect and disconnect on
+      --netns <NAME>     run the diagnostics (D) inside of the network namespace NAME
+      --prune-after <DAYS>
+                         saved networks unused for longer are picked by the
+                         prune preview (p in the saved list), 90 by default
+      --portal-browser <CMD>
+                         command opening the sign-in page of captive portals,
+                         like \"firefox\" or \"xdg-open\"
+      --portal-url <URL>  plain http page leading to the sign-in page of captive
+                         portals, the connectivity check uri of NetworkManager
+                         by default
+      --log-file <PATH>  append a log of every nmcli call and view change to PATH
+      --log-level <LVL>  error, warn, info (default) or debug, logs to
+                         $XDG_STATE_HOME/nmtui/nmtui.log unless --log-file is given
+  -h, --help             print this help
+  -V, --version          print the version";
+
+#[derive(Debug, Default)]
+pub struct Args {
+    // wireless interface passed to nmcli as `ifname`, None lets NetworkManager pick one
+    pub ifname: Option<String>,
+    // logging is off unless one of these is given
+    pub log_file: Option<PathBuf>,
+    pub log_level: Option<LogLevel>,
+    // command the sign-in page of a captive portal is opened with, the url is added to its arguments
+    pub portal_browser: Option<String>,
+    // page leading to the sign-in page, None uses the connectivity check uri of NetworkManager
+    pub portal_url: Option<String>,
+    // network namespace the diagnostics run in, to try them against a test network
+    pub netns: Option<String>,
+    // age in days after which saved networks are picked by the prune preview
+    pub prune_after: Option<u64>,
+}
+
+// Parse the command line arguments
+// --help and --version are handled here and exit right away
+pub fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut argv = env::args().skip(1);
+
+    while let Some(arg) = argv.next() {
+        match arg.as_str() {
+            "-i" | "--ifname" => {
+                let ifname = argv
+                    .next()
+                    .ok_or_else(|| format!("{} needs a device name\n\n{}", arg, USAGE))?;
+                args.ifname = Some(ifname);
+            }
+            "--netns" => {
+                let netns = argv
+                    .next()
+                    .ok_or_else(|| format!("{} needs a namespace name\n\n{}", arg, USAGE))?;
+                args.netns = Some(netns);
+            }
+            "--prune-after" => {
+                let days = argv
+                    .next()
+                    .ok_or_else(|| format!("{} needs a number of days\n\n{}", arg, USAGE))?;
+                args.prune_after = Some(parse_days(&days)?);
+            }
+            "--portal-browser" => {
+                let browser = argv
+                    .next()
+                    .ok_or_else(|| format!("{} needs a command\n\n{}", arg, USAGE))?;
+                args.portal_browser = Some(browser);
+            }
+            "--portal-url" => {
+                let url = argv
+                    .next()
+                    .ok_or_else(|| format!("{} needs a url\n\n{}", arg, USAGE))?;
+                args.portal_url = Some(url);
+            }
+            "--log-file" => {
+                let path = argv
+                    .next()
+                    .ok_or_else(|| format!("{} needs a path\n\n{}", arg, USAGE))?;
+                args.log_file = Some(PathBuf::from(path));
+            }
+            "--log-level" => {
+                let level = argv
+                    .next()
+                    .ok_or_else(|| format!("{} needs a level\n\n{}", arg, USAGE))?;
+                args.log_level = Some(level.parse()?);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "-V" | "--version" => {
+                println!("nmtui {}", env!("CARGO_PKG_VERSION"));
+                std::process::exit(0);
+            }
+            _ => {
+                if let Some(ifname) = arg.strip_prefix("--ifname=") {
+                    args.ifname = Some(ifname.to_string());
+                } else if let Some(netns) = arg.strip_prefix("--netns=") {
+                    args.netns = Some(netns.to_string());
+                } else if let Some(days) = arg.strip_prefix("--prune-after=") {
+                    args.prune_after = Some(parse_days(days)?);
+                } else if let Some(browser) = arg.strip_prefix("--portal-browser=") {
+                    args.portal_browser = Some(browser.to_string());
+                } else if let Some(url) = arg.strip_prefix("--portal-url=") {
+                    args.portal_url = Some(url.to_string());
+                } else if let Some(path) = arg.strip_prefix("--log-file=") {
+                    args.log_file = Some(PathBuf::from(path));
+                } else if let Some(level) = arg.strip_prefix("--log-level=") {
+                    args.log_level = Some(level.parse()?);
+                } else {
+                    return Err(format!("unknown argument '{}'\n\n{}", arg, USAGE));
+                }
+            }
+        }
+    }
+    Ok(args)
+}
+
+fn parse_days(days: &str) -> Result<u64, String> {
+    match days.parse::<u64>() {
+        Ok(days) if days > 0 => Ok(days),
+        _ => Err(format!(
+            "'{}' is not a number of days, like 90\n\n{}",
+            days, USAGE
+        )),
+    }
+}
@@ -0,0 +1,195 @@
+use crate::apps::handlers::status::Status;
+use crate::error::Error;
+use crate::utils::logger::{self, LogLevel};
+use std::io::{self, Write};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+// arguments of nmcli that are followed by a secret, their values never reach the log. Settings are
+// matched with and without their prefix, e.g. "psk" also covers "wifi-sec.psk".
+const SECRET_KEYS: [&str; 17] = [
+    "password",
+    "password-raw",
+    "psk",
+    "pin",
+    "private-key",
+    "private-key-password",
+    "phase2-private-key-password",
+    "ca-cert-password",
+    "client-cert-password",
+    "phase2-ca-cert-password",
+    "phase2-client-cert-password",
+    "leap-password",
+    "secrets",
+    "wep-key0",
+    "wep-key1",
+    "wep-key2",
+    "wep-key3",
+];
+
+// Have a backend command print its messages untranslated, they are matched against the english
+// text (failure kinds, bulk deletes, monitor events). C.UTF-8 rather than C keeps SSIDs and
+// names outside of ascii intact.
+pub fn untranslated(command: &mut Command) -> &mut Command {
+    command.env("LC_ALL", "C.UTF-8")
+}
+
+// Run a backend command to completion and log how it went
+pub fn run(command: &mut Command) -> io::Result<Output> {
+    let started = Instant::now();
+    let output = untranslated(command).output();
+    log_command(command, output.as_ref(), started.elapsed());
+    output
+}
+
+// Run a backend command with `input` piped to its stdin, for secrets that must not show up in
+// the process list. The input is never logged.
+pub fn run_with_input(command: &mut Command, input: &str) -> io::Result<Output> {
+    let started = Instant::now();
+    let output = untranslated(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(input.as_bytes())?;
+                // stdin is dropped here so that the command sees the end of the input
+            }
+            child.wait_with_output()
+        });
+    log_command(command, output.as_ref(), started.elapsed());
+    output
+}
+
+// Run a backend command and turn a failure to start it or a non zero exit into an error
+pub fn run_checked(command: &mut Command) -> Result<Output, Error> {
+    let output = run(command)?;
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(Error::from_output(&output))
+    }
+}
+
+// Run a backend command whose result is shown to the user: `success` when it exits with 0,
+// otherwise what it printed on stderr
+pub fn run_status(command: &mut Command, success: String) -> Status {
+    output_status(run(command), success)
+}
+
+// The status of a command that was already run. A command that could not be started is a
+// failure too, with the same message as the other errors.
+pub fn output_status(output: io::Result<Output>, success: String) -> Status {
+    match output {
+        Ok(output) if output.status.success() => Status::new(success, output.status),
+        Ok(output) => Status::new(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+            output.status,
+        ),
+        Err(e) => Error::from(e).into(),
+    }
+}
+
+// Record a finished command: the redacted command line, how long it took, its exit status and,
+// when it failed, its stderr. Failures are errors, so that they are still logged with
+// --log-level error.
+fn log_command(command: &Command, output: Result<&Output, &io::Error>, duration: Duration) {
+    if !logger::enabled(LogLevel::Error) {
+        return;
+    }
+    let command_line = redacted_command_line(command);
+    let millis = duration.as_millis();
+    match output {
+        Ok(output) if output.status.success() => {
+            logger::info(&format!(
+                "`{}` finished in {}ms with {}",
+                command_line, millis, output.status
+            ));
+            // -s prints the secrets themselves
+            if logger::enabled(LogLevel::Debug) && !shows_secrets(command) {
+                logger::debug(&format!(
+                    "`{}` stdout: {}",
+                    command_line,
+                    String::from_utf8_lossy(&output.stdout).trim_end()
+                ));
+            }
+        }
+        Ok(output) => {
+            logger::error(&format!(
+                "`{}` failed in {}ms with {}: {}",
+                command_line,
+                millis,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            ));
+        }
+        Err(e) => {
+            logger::error(&format!(
+                "`{}` could not be started after {}ms: {}",
+                command_line, millis, e
+            ));
+        }
+    }
+}
+
+fn shows_secrets(command: &Command) -> bool {
+    command
+        .get_args()
+        .any(|arg| arg == "-s" || arg == "--show-secrets")
+}
+
+pub fn is_secret_key(arg: &str) -> bool {
+    SECRET_KEYS
+        .iter()
+        .any(|key| arg == *key || arg.ends_with(&format!(".{}", key)))
+}
+
+fn redacted_command_line(command: &Command) -> String {
+    let mut parts = vec![command.get_program().to_string_lossy().into_owned()];
+    let mut redact_next = false;
+    let mut previous = String::new();
+    for arg in command.get_args() {
+        let arg = arg.to_string_lossy();
+        if redact_next {
+            parts.push("<redacted>".to_string());
+        } else {
+            parts.push(arg.to_string());
+        }
+        // "-g wifi-sec.psk" names the field to print, it is not followed by its value
+        redact_next = !redact_next && is_secret_key(&arg) && previous != "-g" && previous != "-f";
+        previous = arg.into_owned();
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs, process};
+
+    #[test]
+    fn failures_are_logged_at_error_level() {
+        let path = env::temp_dir().join(format!("nmtui-command-test-{}.log", process::id()));
+        let _ = fs::remove_file(&path);
+        logger::init(&path, LogLevel::Error).unwrap();
+
+        let _ = run(Command::new("sh").args(["-c", "exit 0"]));
+        let _ = run(Command::new("sh").args(["-c", "echo boom >&2; exit 3"]));
+        let _ = run(&mut Command::new("nmtui-command-that-does-not-exist"));
+
+        let log = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        // the logger is shared with the other tests, only the lines of these commands count
+        let lines: Vec<&str> = log
+            .lines()
+            .filter(|line| line.contains("`sh -c") || line.contains("does-not-exist"))
+            .collect();
+        assert_eq!(lines.len(), 2, "{}", log);
+        assert!(lines[0].contains("ERROR `sh -c echo boom >&2; exit 3` failed in"));
+        assert!(lines[0].ends_with(": boom"));
+        assert!(
+            lines[1].contains("ERROR `nmtui-command-that-does-not-exist` could not be started")
+        );
+    }
+}
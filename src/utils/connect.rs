@@ -1,70 +1,105 @@
 use crate::apps::handlers::WifiInputState;
 use crate::apps::handlers::flags::Flags;
 use crate::apps::handlers::status::Status;
-use std::process::{Command, ExitStatus};
+use crate::utils::command::run_status;
+use crate::utils::failure::FailureKind;
+use std::process::Command;
 
 // Connect to a saved network without password
-pub fn connect_to_saved_network(ssid: &str) -> Status {
-    let output = Command::new("nmcli")
-        .args(["dev", "wifi", "connect", ssid])
-        .output();
-    match output {
-        Ok(output) => {
-            let status = output.status;
-            if status.success() {
-                let stdout = format!("Successfully connected to '{}'", ssid);
-                Status::new(stdout.to_string(), status)
-            } else {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                Status::new(stderr.to_string(), status)
-            }
-        }
-        Err(e) => Status::new(
-            format!("Failed to execute nmcli: {}", e),
-            ExitStatus::default(),
-        ),
+pub fn connect_to_saved_network(ssid: &str, ifname: Option<&str>) -> Status {
+    let mut command = Command::new("nmcli");
+    command.args(["dev", "wifi", "connect", ssid]);
+    if let Some(ifname) = ifname {
+        command.args(["ifname", ifname]);
+    }
+    run_status(
+        &mut command,
+        format!("Successfully connected to '{}'", ssid),
+    )
+}
+
+// Bring up a saved profile by its uuid, so that profiles sharing a name are told apart
+// nmcli connection up uuid <UUID> [ifname <DEVICE>]
+pub fn activate_saved_profile(name: &str, uuid: &str, ifname: Option<&str>) -> Status {
+    let mut command = Command::new("nmcli");
+    command.args(["connection", "up", "uuid", uuid]);
+    if let Some(ifname) = ifname {
+        command.args(["ifname", ifname]);
     }
+    run_status(
+        &mut command,
+        format!("Successfully connected to '{}'", name),
+    )
 }
 
 // Connect to a network with given credentials
-pub fn connect_to_network(wifi_creadentials: &WifiInputState) -> Status {
+pub fn connect_to_network(wifi_creadentials: &WifiInputState, ifname: Option<&str>) -> Status {
     let WifiInputState {
         flags: Flags { is_hidden, .. },
         ssid,
         password,
+        profile,
         ..
     } = wifi_creadentials;
 
-    let output = if *is_hidden {
+    let mut status = match profile {
+        Some((name, uuid)) => retry_saved_profile(name, uuid, password, ifname),
+        None => connect_with_password(ssid, password, *is_hidden, ifname),
+    };
+    // secrets were given, so nmcli asking for them again means they were rejected
+    if status.failure == Some(FailureKind::SecretsRequired) && !password.is_empty() {
+        status.failure = Some(FailureKind::WrongPassword);
+    }
+    status
+}
+
+// nmcli dev wifi connect <SSID> password <PASSWORD> [hidden yes] [ifname <DEVICE>]
+fn connect_with_password(
+    ssid: &str,
+    password: &str,
+    is_hidden: bool,
+    ifname: Option<&str>,
+) -> Status {
+    let mut command = Command::new("nmcli");
+    if is_hidden {
         // connecting to the hidden network
-        Command::new("nmcli")
-            .args([
-                "dev", "wifi", "connect", ssid, "password", password, "hidden", "yes",
-            ])
-            .output()
+        command.args([
+            "dev", "wifi", "connect", ssid, "password", password, "hidden", "yes",
+        ]);
     } else {
         // connecting to the secured network with password
-        Command::new("nmcli")
-            .args(["dev", "wifi", "connect", ssid, "password", password])
-            .output()
-    };
+        command.args(["dev", "wifi", "connect", ssid, "password", password]);
+    }
+    if let Some(ifname) = ifname {
+        command.args(["ifname", ifname]);
+    }
+    // the stdout of nmcli is not shown, it glitched the ui when connecting successfully
+    run_status(
+        &mut command,
+        format!("Successfully connected to '{}'", ssid),
+    )
+}
 
-    match output {
-        Ok(output) => {
-            let status = output.status;
-            if status.success() {
-                // here this thing is creating some glitch in the ui when connecting successfully
-                // let stdout = String::from_utf8_lossy(&output.stdout);
-                let stdout = format!("Successfully connected to '{}'", ssid);
-                Status::new(stdout.to_string(), status)
-            } else {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                Status::new(stderr.to_string(), status)
-            }
+// Store the password entered again in the saved profile that was rejected, then bring that
+// profile up. Connecting by ssid could pick or create another profile of the same network.
+// nmcli connection modify uuid <UUID> wifi-sec.psk <PASSWORD>
+// nmcli connection up uuid <UUID> [ifname <DEVICE>]
+fn retry_saved_profile(name: &str, uuid: &str, password: &str, ifname: Option<&str>) -> Status {
+    if !password.is_empty() {
+        let status = run_status(
+            Command::new("nmcli").args([
+                "connection",
+                "modify",
+                "uuid",
+                uuid,
+                "wifi-sec.psk",
+                password,
+            ]),
+            String::new(),
+        );
+        if !status.status_code.success() {
+            return status;
         }
-        Err(e) => Status::new(
-            format!("Failed to execute nmcli: {}", e),
-            ExitStatus::default(),
-        ),
     }
+    activate_saved_profile(name, uuid, ifname)
 }
@@ -0,0 +1,168 @@
+use crate::utils::command::run;
+use crate::utils::terse::{get_values, records};
+use std::fmt;
+use std::process::Command;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::thread;
+
+// Global connectivity state as reported by `nmcli networking connectivity`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Full,
+    Limited,
+    Portal,
+    None,
+    #[default]
+    Unknown,
+}
+
+impl Connectivity {
+    fn parse(value: &str) -> Self {
+        match value.trim() {
+            "full" => Connectivity::Full,
+            "limited" => Connectivity::Limited,
+            "portal" => Connectivity::Portal,
+            "none" => Connectivity::None,
+            _ => Connectivity::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Connectivity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Connectivity::Full => "full",
+            Connectivity::Limited => "limited",
+            Connectivity::Portal => "portal",
+            Connectivity::None => "none",
+            Connectivity::Unknown => "unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// What the status bar shows about the current wifi connection
+#[derive(Debug, Default, Clone)]
+pub struct ConnectionInfo {
+    // wireless device that is connected, None when no wifi device is
+    pub device: Option<String>,
+    // name of the profile active on the device
+    pub connection: Option<String>,
+    // first IPv4 address of the device with its prefix, like 192.168.1.5/24
+    pub ip_address: Option<String>,
+    pub connectivity: Connectivity,
+}
+
+// Re-read the connection info in the background, so the nmcli calls do not stall the ui
+//
+// Failures leave the fields empty, the status bar then shows that nothing is connected.
+pub fn refresh_connection_info(info: Arc<RwLock<ConnectionInfo>>, ifname: Option<String>) {
+    thread::spawn(move || {
+        let connection_info = connection_info(ifname.as_deref());
+        *info.write().unwrap_or_else(PoisonError::into_inner) = connection_info;
+    });
+}
+
+fn connection_info(ifname: Option<&str>) -> ConnectionInfo {
+    let mut info = ConnectionInfo {
+        connectivity: connectivity(&["networking", "connectivity"]),
+        ..Default::default()
+    };
+    if let Some((device, connection)) = connected_wifi_device(ifname) {
+        info.ip_address = ip_address(&device);
+        info.device = Some(device);
+        info.connection = Some(connection);
+    }
+    info
+}
+
+// Ask NetworkManager to probe the internet again instead of returning the last known state,
+// this waits for the probe so it is only used right after connecting
+// nmcli networking connectivity check
+pub fn check_connectivity() -> Connectivity {
+    connectivity(&["networking", "connectivity", "check"])
+}
+
+// nmcli networking connectivity [check]
+fn connectivity(args: &[&str]) -> Connectivity {
+    run(Command::new("nmcli").args(args))
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| Connectivity::parse(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+// The connected wireless device and its profile, the chosen interface when there is one
+// nmcli -t -f DEVICE,TYPE,STATE,CONNECTION device status
+fn connected_wifi_device(ifname: Option<&str>) -> Option<(String, String)> {
+    let output = run(Command::new("nmcli").args([
+        "-t",
+        "-f",
+        "DEVICE,TYPE,STATE,CONNECTION",
+        "device",
+        "status",
+    ]))
+    .ok()
+    .filter(|output| output.status.success())?;
+    wifi_device(&String::from_utf8_lossy(&output.stdout), ifname)
+}
+
+fn wifi_device(output: &str, ifname: Option<&str>) -> Option<(String, String)> {
+    records::<4>(output)
+        .ok()?
+        .into_iter()
+        .find(|[device, device_type, state, _]| {
+            device_type == "wifi"
+                && state == "connected"
+                && ifname.is_none_or(|ifname| ifname == device)
+        })
+        .map(|[device, _, _, connection]| (device, connection))
+}
+
+// nmcli -g IP4.ADDRESS device show <DEVICE>
+fn ip_address(device: &str) -> Option<String> {
+    let output = run(Command::new("nmcli").args(["-g", "IP4.ADDRESS", "device", "show", device]))
+        .ok()
+        .filter(|output| output.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // several addresses are printed on one line separated by " | "
+    get_values(&stdout)
+        .first()
+        .and_then(|addresses| addresses.split(" | ").next())
+        .filter(|address| !address.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_connectivity_states() {
+        assert_eq!(Connectivity::parse("full\n"), Connectivity::Full);
+        assert_eq!(Connectivity::parse("limited"), Connectivity::Limited);
+        assert_eq!(Connectivity::parse("portal"), Connectivity::Portal);
+        assert_eq!(Connectivity::parse("none"), Connectivity::None);
+        assert_eq!(Connectivity::parse("unknown"), Connectivity::Unknown);
+        assert_eq!(Connectivity::parse(""), Connectivity::Unknown);
+    }
+
+    #[test]
+    fn finds_the_connected_wifi_device() {
+        let output = "eth0:ethernet:connected:Wired connection 1\n\
+            wlan0:wifi:disconnected:\n\
+            wlan1:wifi:connected:Cafe\\:2nd floor\n\
+            lo:loopback:connected (externally):lo\n";
+        assert_eq!(
+            wifi_device(output, None),
+            Some(("wlan1".to_string(), "Cafe:2nd floor".to_string()))
+        );
+        assert_eq!(
+            wifi_device(output, Some("wlan1")).map(|(device, _)| device),
+            Some("wlan1".to_string())
+        );
+        // the chosen interface is not connected, another one does not count
+        assert_eq!(wifi_device(output, Some("wlan0")), None);
+    }
+}
@@ -1,13 +1,325 @@
-use std::process::Command;
-use std::thread;
-
-pub fn delete_connection(ssid: String) {
-    // nmcli connection delete id "<SSID>"
-    thread::spawn(move || {
-        let output = Command::new("nmcli")
-            .args(["connection", "delete", "id", ssid.trim()])
-            .output()
-            .expect(" Failed to execute nmcli command");
-        if !output.status.success() {}
-    });
+use crate::apps::handlers::status::Status;
+use crate::error::{Error, Result};
+use crate::utils::command::{is_secret_key, run, run_checked, run_status, run_with_input};
+use crate::utils::terse::split_fields;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, ExitStatus};
+use std::time::Duration;
+
+// how long a deleted connection can still be restored
+pub const UNDO_TIMEOUT: Duration = Duration::from_secs(30);
+
+// properties nmcli prints but refuses to set, they are filled in by NetworkManager
+const READ_ONLY_PROPERTIES: [&str; 3] = [
+    "connection.timestamp",
+    "connection.read-only",
+    "802-11-wireless.seen-bssids",
+];
+
+// Every setting of a profile, captured before it is deleted so that it can be created again
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedProfile {
+    pub name: String,
+    connection_type: String,
+    // setting.property and its value, in the order nmcli printed them
+    settings: Vec<(String, String)>,
+}
+
+// nmcli connection delete uuid <UUID>
+pub fn delete_connection(uuid: &str) -> Result<()> {
+    run_checked(Command::new("nmcli").args(["connection", "delete", "uuid", uuid]))?;
+    Ok(())
+}
+
+// What happened to each of several connections deleted at once
+#[derive(Debug, Default, PartialEq)]
+pub struct BulkDelete {
+    pub deleted: Vec<String>,
+    // name of the connection and why it was not deleted
+    pub failed: Vec<(String, String)>,
+}
+
+// Delete several connections, given by name and uuid, with a single nmcli call. The ones that
+// fail do not stop the others.
+// nmcli connection delete uuid <UUID> uuid <UUID> ...
+pub fn delete_connections(profiles: &[(String, String)]) -> Result<BulkDelete> {
+    let mut command = Command::new("nmcli");
+    command.args(["connection", "delete"]);
+    for (_, uuid) in profiles {
+        command.args(["uuid", uuid]);
+    }
+    // nmcli exits with an error when any of them failed, the output tells which
+    let output = run(&mut command)?;
+    Ok(bulk_delete_summary(
+        profiles,
+        &String::from_utf8_lossy(&output.stdout),
+        &String::from_utf8_lossy(&output.stderr),
+    ))
+}
+
+// nmcli prints "Connection 'NAME' (UUID) successfully deleted." for every deleted connection and
+// an error like "Error: unknown connection 'UUID'." for the others
+fn bulk_delete_summary(profiles: &[(String, String)], stdout: &str, stderr: &str) -> BulkDelete {
+    let mut summary = BulkDelete::default();
+    for (name, uuid) in profiles {
+        let deleted = stdout.lines().any(|line| {
+            line.contains(&format!("({})", uuid)) && line.contains("successfully deleted")
+        });
+        if deleted {
+            summary.deleted.push(name.clone());
+        } else {
+            let reason = stderr
+                .lines()
+                .find(|line| line.contains(uuid.as_str()))
+                .or_else(|| stderr.lines().rfind(|line| !line.trim().is_empty()))
+                .unwrap_or("not deleted")
+                .trim()
+                .trim_start_matches("Error: ")
+                .to_string();
+            summary.failed.push((name.clone(), reason));
+        }
+    }
+    summary
+}
+
+// Read a profile with its secrets, by uuid since several profiles can share a name
+// nmcli --show-secrets -t connection show uuid <UUID>
+pub fn capture_profile(name: &str, uuid: &str) -> Result<SavedProfile> {
+    let output = run_checked(Command::new("nmcli").args([
+        "--show-secrets",
+        "-t",
+        "connection",
+        "show",
+        "uuid",
+        uuid,
+    ]))?;
+    parse_profile(name.trim(), &String::from_utf8_lossy(&output.stdout))
+}
+
+// Create a deleted profile again, with the same uuid so that anything referring to it still works
+// nmcli connection add type <TYPE> <setting.property> <value> ...
+//
+// The secrets are left out of the arguments, which any local user can read from the process
+// list, and are set afterwards through stdin with restore_secrets.
+pub fn restore_profile(profile: &SavedProfile) -> Status {
+    let mut command = Command::new("nmcli");
+    command.args(["connection", "add", "type", &profile.connection_type]);
+    for (property, value) in profile
+        .settings
+        .iter()
+        .filter(|(property, _)| !is_secret_key(property))
+    {
+        command.args([property, value]);
+    }
+    let status = run_status(&mut command, format!("Restored '{}'", profile.name));
+    if !status.status_code.success() {
+        return status;
+    }
+    match restore_secrets(profile) {
+        Ok(()) => status,
+        Err(reason) => Status::new(
+            format!(
+                "Restored '{}' without its secrets: {}",
+                profile.name, reason
+            ),
+            ExitStatus::from_raw(1 << 8),
+        ),
+    }
+}
+
+// Set the secrets of a restored profile with the nmcli editor, which reads its commands on stdin
+// nmcli connection edit uuid <UUID>
+fn restore_secrets(profile: &SavedProfile) -> std::result::Result<(), String> {
+    let Some(input) = secrets_input(profile) else {
+        return Ok(());
+    };
+    let uuid = profile.uuid().unwrap_or_default();
+    let output = run_with_input(
+        Command::new("nmcli").args(["connection", "edit", "uuid", uuid]),
+        &input,
+    )
+    .map_err(|e| format!("failed to execute nmcli: {}", e))?;
+    // the editor exits with 0 even when a command failed, it only prints the error
+    let errors = String::from_utf8_lossy(&output.stderr);
+    match errors.lines().find(|line| line.contains("Error")) {
+        Some(error) if output.status.success() => Err(error.trim().to_string()),
+        _ if !output.status.success() => Err(errors.trim().to_string()),
+        _ => Ok(()),
+    }
+}
+
+// Commands for the nmcli editor setting every secret of the profile, None when it has none.
+// The confirmation asked before saving a profile that connects automatically is turned off, it
+// would read the next command as its answer.
+fn secrets_input(profile: &SavedProfile) -> Option<String> {
+    let secrets: Vec<&(String, String)> = profile
+        .settings
+        .iter()
+        .filter(|(property, _)| is_secret_key(property))
+        .collect();
+    if secrets.is_empty() {
+        return None;
+    }
+    let mut input = "nmcli save-confirmation no\n".to_string();
+    for (property, value) in secrets {
+        input.push_str(&format!("set {} {}\n", property, value));
+    }
+    input.push_str("save\nquit\n");
+    Some(input)
+}
+
+impl SavedProfile {
+    pub fn uuid(&self) -> Option<&str> {
+        self.settings
+            .iter()
+            .find(|(property, _)| property == "connection.uuid")
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+// The settings are the lowercase `setting.property:value` lines, the uppercase ones (GENERAL,
+// IP4, DHCP4, ...) describe the active connection and are skipped
+fn parse_profile(name: &str, output: &str) -> Result<SavedProfile> {
+    let mut connection_type = None;
+    let mut settings = Vec::new();
+
+    for line in output.lines().filter(|line| !line.is_empty()) {
+        let fields = split_fields(line);
+        let [property, value] = fields.as_slice() else {
+            return Err(Error::Parse(format!("unexpected profile line: {}", line)));
+        };
+        if !property.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit()) {
+            continue;
+        }
+        if property == "connection.type" {
+            connection_type = Some(value.clone());
+            continue;
+        }
+        if value.is_empty() || value == "--" || READ_ONLY_PROPERTIES.contains(&property.as_str()) {
+            continue;
+        }
+        settings.push((property.clone(), setting_value(value)));
+    }
+
+    let connection_type = connection_type
+        .ok_or_else(|| Error::Parse(format!("'{}' has no connection type", name)))?;
+    Ok(SavedProfile {
+        name: name.to_string(),
+        connection_type,
+        settings,
+    })
+}
+
+// Flags and enums are printed with their name, like "0 (none)", only the number is accepted back
+fn setting_value(value: &str) -> String {
+    match value.split_once(" (") {
+        Some((number, _)) if value.ends_with(')') && number.parse::<i64>().is_ok() => {
+            number.to_string()
+        }
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILE: &str = "connection.id:Office\n\
+        connection.uuid:0b7c9a53-3c1e-4d8e-9a39-2f6a4f0e6a11\n\
+        connection.type:802-11-wireless\n\
+        connection.interface-name:\n\
+        connection.timestamp:1718000000\n\
+        connection.autoconnect-slaves:-1 (default)\n\
+        802-11-wireless.ssid:Office\n\
+        802-11-wireless.seen-bssids:AA\\:BB\\:CC\\:DD\\:EE\\:FF\n\
+        802-11-wireless-security.key-mgmt:wpa-psk\n\
+        802-11-wireless-security.psk:pass\\:word\n\
+        802-11-wireless-security.psk-flags:0 (none)\n\
+        ipv4.dns-search:--\n\
+        GENERAL.NAME:Office\n\
+        IP4.ADDRESS[1]:192.168.1.5/24\n";
+
+    #[test]
+    fn keeps_the_settings_and_secrets() {
+        let profile = parse_profile("Office", PROFILE).unwrap();
+        assert_eq!(profile.connection_type, "802-11-wireless");
+        let settings: Vec<(&str, &str)> = profile
+            .settings
+            .iter()
+            .map(|(property, value)| (property.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(
+            settings,
+            [
+                ("connection.id", "Office"),
+                ("connection.uuid", "0b7c9a53-3c1e-4d8e-9a39-2f6a4f0e6a11"),
+                ("connection.autoconnect-slaves", "-1"),
+                ("802-11-wireless.ssid", "Office"),
+                ("802-11-wireless-security.key-mgmt", "wpa-psk"),
+                ("802-11-wireless-security.psk", "pass:word"),
+                ("802-11-wireless-security.psk-flags", "0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn secrets_are_kept_for_stdin() {
+        let profile = parse_profile("Office", PROFILE).unwrap();
+        assert_eq!(profile.uuid(), Some("0b7c9a53-3c1e-4d8e-9a39-2f6a4f0e6a11"));
+        assert_eq!(
+            secrets_input(&profile).as_deref(),
+            Some(
+                "nmcli save-confirmation no\n\
+                set 802-11-wireless-security.psk pass:word\n\
+                save\nquit\n"
+            )
+        );
+        // psk-flags only says where the secret is kept, it is no secret itself
+        assert!(!is_secret_key("802-11-wireless-security.psk-flags"));
+
+        let open = parse_profile("Cafe", "connection.type:802-11-wireless\n").unwrap();
+        assert_eq!(secrets_input(&open), None);
+    }
+
+    #[test]
+    fn profile_without_type_is_an_error() {
+        assert!(parse_profile("Office", "connection.id:Office\n").is_err());
+    }
+
+    #[test]
+    fn summarizes_a_bulk_delete() {
+        let profile = |name: &str, uuid: &str| (name.to_string(), uuid.to_string());
+        // two profiles share the name Guest, only the one whose uuid is in the output is deleted
+        let profiles = [
+            profile("Guest", "5d1c7f36-6a39-4c5b-9c1f-7a4f0e6a1111"),
+            profile("Airport", "7e0f3a2b-1c4d-4e5f-8a9b-0c1d2e3f4a5b"),
+            profile("Guest", "0b7c9a53-3c1e-4d8e-9a39-2f6a4f0e6a11"),
+        ];
+        let stdout =
+            "Connection 'Guest' (5d1c7f36-6a39-4c5b-9c1f-7a4f0e6a1111) successfully deleted.\n";
+        let stderr = "Error: unknown connection '7e0f3a2b-1c4d-4e5f-8a9b-0c1d2e3f4a5b'.\n\
+            Error: not all connections deleted.\n";
+        assert_eq!(
+            bulk_delete_summary(&profiles, stdout, stderr),
+            BulkDelete {
+                deleted: vec!["Guest".to_string()],
+                failed: vec![
+                    (
+                        "Airport".to_string(),
+                        "unknown connection '7e0f3a2b-1c4d-4e5f-8a9b-0c1d2e3f4a5b'.".to_string()
+                    ),
+                    (
+                        "Guest".to_string(),
+                        "not all connections deleted.".to_string()
+                    ),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn named_values_keep_their_text() {
+        assert_eq!(setting_value("wpa-psk"), "wpa-psk");
+        assert_eq!(setting_value("Cafe (2nd floor)"), "Cafe (2nd floor)");
+    }
 }
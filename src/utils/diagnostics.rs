@@ -0,0 +1,389 @@
+use crate::utils::command::run;
+use crate::utils::connection_info::{Connectivity, check_connectivity};
+use std::process::Command;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::thread;
+
+// name looked up by the dns check
+pub const DNS_PROBE_HOST: &str = "example.com";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Link,
+    Address,
+    DefaultRoute,
+    Gateway,
+    Dns,
+    Connectivity,
+}
+
+impl Check {
+    // in the order they are run
+    pub const ALL: [Check; 6] = [
+        Check::Link,
+        Check::Address,
+        Check::DefaultRoute,
+        Check::Gateway,
+        Check::Dns,
+        Check::Connectivity,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Check::Link => "Link up",
+            Check::Address => "IP address assigned",
+            Check::DefaultRoute => "Default route present",
+            Check::Gateway => "Gateway reachable",
+            Check::Dns => "DNS resolves",
+            Check::Connectivity => "NetworkManager connectivity",
+        }
+    }
+
+    // the check whose result this one needs, the dns and connectivity checks run regardless so
+    // that a gateway ignoring pings does not hide whether the internet works
+    fn depends_on(&self) -> Option<Check> {
+        match self {
+            Check::Address | Check::DefaultRoute => Some(Check::Link),
+            Check::Gateway => Some(Check::DefaultRoute),
+            Check::Link | Check::Dns | Check::Connectivity => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail,
+    // not run because a check it depends on failed, or because there is nothing to check
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub check: Check,
+    pub outcome: Outcome,
+    pub details: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    // results of the checks that finished, in the order of Check::ALL
+    pub results: Vec<CheckResult>,
+    pub running: bool,
+}
+
+// Where the checks run, the host or a network namespace created with `ip netns add`
+#[derive(Debug, Clone)]
+struct Target {
+    ifname: Option<String>,
+    netns: Option<String>,
+}
+
+impl Target {
+    // `ip netns exec <NAME> <PROGRAM>` inside of a namespace
+    fn command(&self, program: &str) -> Command {
+        match &self.netns {
+            Some(netns) => {
+                let mut command = Command::new("ip");
+                command.args(["netns", "exec", netns, program]);
+                command
+            }
+            None => Command::new(program),
+        }
+    }
+
+    // stdout of the command, or what went wrong as the details of the check
+    fn output(&self, program: &str, args: &[&str]) -> Result<String, String> {
+        match run(self.command(program).args(args)) {
+            Ok(output) if output.status.success() => {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let message = if stderr.trim().is_empty() {
+                    stdout
+                } else {
+                    stderr
+                };
+                Err(format!(
+                    "`{}` failed: {}",
+                    program,
+                    message.trim().lines().last().unwrap_or_default()
+                ))
+            }
+            Err(e) => Err(format!("`{}` could not be started: {}", program, e)),
+        }
+    }
+}
+
+// Run the checks one after another in the background, every result is added as soon as it is known
+pub fn run_diagnostics(
+    diagnostics: Arc<RwLock<Diagnostics>>,
+    ifname: Option<String>,
+    netns: Option<String>,
+) {
+    {
+        let mut diagnostics = diagnostics.write().unwrap_or_else(PoisonError::into_inner);
+        diagnostics.results.clear();
+        diagnostics.running = true;
+    }
+    thread::spawn(move || {
+        let target = Target { ifname, netns };
+        let mut state = State::default();
+        for check in Check::ALL {
+            let result = state.run(&target, check);
+            diagnostics
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .results
+                .push(result);
+        }
+        diagnostics
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .running = false;
+    });
+}
+
+// What the earlier checks found out, used by the later ones
+#[derive(Debug, Default)]
+struct State {
+    device: Option<String>,
+    // None for a default route without a gateway, like the one of a full tunnel vpn
+    gateway: Option<String>,
+    // checks that failed or were skipped, the ones depending on them are skipped
+    failed: Vec<Check>,
+}
+
+impl State {
+    fn run(&mut self, target: &Target, check: Check) -> CheckResult {
+        let outcome = match check.depends_on() {
+            Some(dependency) if self.failed.contains(&dependency) => Err((
+                Outcome::Skipped,
+                format!("needs \"{}\"", dependency.label().to_lowercase()),
+            )),
+            _ => self.check(target, check),
+        };
+        match outcome {
+            Ok(details) => CheckResult {
+                check,
+                outcome: Outcome::Pass,
+                details,
+            },
+            Err((outcome, details)) => {
+                self.failed.push(check);
+                CheckResult {
+                    check,
+                    outcome,
+                    details,
+                }
+            }
+        }
+    }
+
+    fn check(&mut self, target: &Target, check: Check) -> Result<String, (Outcome, String)> {
+        match check {
+            Check::Gateway if self.gateway.is_none() => Err((
+                Outcome::Skipped,
+                "the default route has no gateway to ping".to_string(),
+            )),
+            // NetworkManager does not run inside of test namespaces, so this one is host only
+            Check::Connectivity if target.netns.is_some() => Err((
+                Outcome::Skipped,
+                "not checked inside of a network namespace".to_string(),
+            )),
+            _ => self
+                .run_check(target, check)
+                .map_err(|details| (Outcome::Fail, details)),
+        }
+    }
+
+    fn run_check(&mut self, target: &Target, check: Check) -> Result<String, String> {
+        match check {
+            // ip -o link show [dev <DEVICE>]
+            Check::Link => {
+                // without a chosen interface the one of the default route is checked, so that
+                // bridges and other interfaces that happen to be up do not hide a down wifi link
+                let ifname = target.ifname.clone().or_else(|| {
+                    let output = target.output("ip", &["route", "show", "default"]).ok()?;
+                    default_route(&output).map(|(_, device)| device)
+                });
+                let mut args = vec!["-o", "link", "show"];
+                if let Some(ifname) = &ifname {
+                    args.extend(["dev", ifname]);
+                }
+                let output = target.output("ip", &args)?;
+                let device = link_up(&output).ok_or_else(|| match &ifname {
+                    Some(ifname) => format!("{} is down", ifname),
+                    None => "no interface is up".to_string(),
+                })?;
+                let details = format!("{} is up", device);
+                self.device = Some(device);
+                Ok(details)
+            }
+            // ip -o -4 addr show dev <DEVICE>
+            Check::Address => {
+                let device = self.device.clone().unwrap_or_default();
+                let output = target.output("ip", &["-o", "-4", "addr", "show", "dev", &device])?;
+                ipv4_address(&output)
+                    .map(|address| format!("{} on {}", address, device))
+                    .ok_or_else(|| format!("{} has no IPv4 address, DHCP may have failed", device))
+            }
+            // ip route show default dev <DEVICE>
+            Check::DefaultRoute => {
+                let device = self.device.clone().unwrap_or_default();
+                let output = target.output("ip", &["route", "show", "default", "dev", &device])?;
+                // the device is left out of the output when it is given
+                let (gateway, _) = default_route(&format!("{} dev {}", output.trim(), device))
+                    .ok_or_else(|| format!("there is no default route through {}", device))?;
+                let details = match &gateway {
+                    Some(gateway) => format!("via {} dev {}", gateway, device),
+                    None => format!("dev {}", device),
+                };
+                self.gateway = gateway;
+                Ok(details)
+            }
+            // ping -c 1 -W 2 <GATEWAY>
+            Check::Gateway => {
+                let gateway = self.gateway.clone().unwrap_or_default();
+                target.output("ping", &["-c", "1", "-W", "2", &gateway])?;
+                Ok(format!("{} answered", gateway))
+            }
+            // getent hosts <HOST>
+            Check::Dns => {
+                let output = target
+                    .output("getent", &["hosts", DNS_PROBE_HOST])
+                    .map_err(|_| format!("{} could not be resolved", DNS_PROBE_HOST))?;
+                let address = output.split_whitespace().next().unwrap_or_default();
+                Ok(format!("{} is {}", DNS_PROBE_HOST, address))
+            }
+            Check::Connectivity => match check_connectivity() {
+                Connectivity::Full => Ok("full".to_string()),
+                connectivity => Err(connectivity.to_string()),
+            },
+        }
+    }
+}
+
+// First interface other than loopback whose link is up
+// "2: wlan0: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500 ... state UP ..."
+fn link_up(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split(": ");
+        let _index = fields.next()?;
+        // devices in a namespace are printed as "veth0@if5"
+        let device = fields.next()?.split('@').next()?;
+        let flags = fields.next()?;
+        let up = flags.contains("LOWER_UP") || line.contains(" state UP ");
+        (up && device != "lo").then(|| device.to_string())
+    })
+}
+
+// "3: wlan0    inet 192.168.1.5/24 brd 192.168.1.255 scope global dynamic wlan0 ..."
+fn ipv4_address(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        words.find(|&word| word == "inet")?;
+        words.next().map(str::to_string)
+    })
+}
+
+// Gateway and device of the first default route, a route of a point to point link like a vpn
+// tunnel has no gateway
+// "default via 192.168.1.1 dev wlan0 proto dhcp metric 600"
+// "default dev wg0 scope link"
+fn default_route(output: &str) -> Option<(Option<String>, String)> {
+    output.lines().find_map(|line| {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let value = |key: &str| {
+            words
+                .windows(2)
+                .find(|pair| pair[0] == key)
+                .map(|pair| pair[1].to_string())
+        };
+        Some((value("via"), value("dev")?))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_first_link_that_is_up() {
+        let output = "1: lo: <LOOPBACK,UP,LOWER_UP> mtu 65536 state UNKNOWN\n\
+            2: eth0: <NO-CARRIER,BROADCAST,UP> mtu 1500 state DOWN\n\
+            3: veth-test@if4: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500 state UP\n";
+        assert_eq!(link_up(output).as_deref(), Some("veth-test"));
+        assert_eq!(
+            link_up("2: eth0: <NO-CARRIER,UP> mtu 1500 state DOWN\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_the_ipv4_address() {
+        let output = "3: wlan0    inet 192.168.1.5/24 brd 192.168.1.255 scope global wlan0\n";
+        assert_eq!(ipv4_address(output).as_deref(), Some("192.168.1.5/24"));
+        assert_eq!(ipv4_address(""), None);
+    }
+
+    #[test]
+    fn reads_the_default_route() {
+        let output = "default via 192.168.1.1 dev wlan0 proto dhcp metric 600\n";
+        assert_eq!(
+            default_route(output),
+            Some((Some("192.168.1.1".to_string()), "wlan0".to_string()))
+        );
+        // a full tunnel vpn routes everything into its interface without a gateway
+        assert_eq!(
+            default_route("default dev wg0 scope link\n"),
+            Some((None, "wg0".to_string()))
+        );
+        assert_eq!(default_route(""), None);
+    }
+
+    #[test]
+    fn only_dependent_checks_are_skipped_after_a_failure() {
+        let target = Target {
+            ifname: None,
+            netns: None,
+        };
+        let mut state = State {
+            failed: vec![Check::Link],
+            ..Default::default()
+        };
+        assert_eq!(state.run(&target, Check::Address).outcome, Outcome::Skipped);
+        assert_eq!(
+            state.run(&target, Check::DefaultRoute).outcome,
+            Outcome::Skipped
+        );
+        // skipped in turn since the default route was
+        assert_eq!(state.run(&target, Check::Gateway).outcome, Outcome::Skipped);
+    }
+
+    #[test]
+    fn a_route_without_gateway_skips_the_ping() {
+        let target = Target {
+            ifname: None,
+            netns: None,
+        };
+        let mut state = State::default();
+        let result = state.run(&target, Check::Gateway);
+        assert_eq!(result.outcome, Outcome::Skipped);
+        assert_eq!(result.details, "the default route has no gateway to ping");
+    }
+
+    #[test]
+    fn connectivity_is_skipped_inside_of_a_namespace() {
+        let target = Target {
+            ifname: None,
+            netns: Some("nmtui-test".to_string()),
+        };
+        let mut state = State::default();
+        let result = state.run(&target, Check::Connectivity);
+        assert_eq!(result.outcome, Outcome::Skipped);
+        assert_eq!(result.details, "not checked inside of a network namespace");
+    }
+}
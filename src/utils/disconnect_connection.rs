@@ -1,40 +1,47 @@
+use crate::utils::command::{output_status, run};
 use crate::{WifiNetwork, apps::handlers::status::Status};
 use std::{
-    process::{Command, ExitStatus},
-    sync::{Arc, RwLock},
+    io,
+    os::unix::process::ExitStatusExt,
+    process::{Command, ExitStatus, Output},
+    sync::{Arc, PoisonError, RwLock},
 };
 
-pub fn disconnect_connected_network(wifi_list: Arc<RwLock<Vec<WifiNetwork>>>) -> Status {
-    let list = wifi_list.read().expect("WifiNetworks lock poisoned");
+pub fn disconnect_connected_network(
+    wifi_list: Arc<RwLock<Vec<WifiNetwork>>>,
+    ifname: Option<&str>,
+) -> Status {
+    // with a chosen interface only that device is disconnected, whatever it is connected to
+    // nmcli device disconnect <DEVICE>
+    if let Some(ifname) = ifname {
+        return disconnect_status(run(Command::new("nmcli").args([
+            "device",
+            "disconnect",
+            ifname,
+        ])));
+    }
+
+    let list = wifi_list.read().unwrap_or_else(PoisonError::into_inner);
 
-    for network in list.iter() {
-        if network.in_use {
-            let ssid = &network.ssid;
-            let output = Command::new("nmcli")
-                .args(["connection", "down", ssid])
-                .output();
-            match output {
-                Ok(output) => {
-                    let status = output.status;
-                    if status.success() {
-                        let stdout = String::from_utf8_lossy(&output.stdout);
-                        return Status::new(stdout.into(), status);
-                    } else {
-                        let stderr = String::from_utf8_lossy(&output.stderr);
-                        return Status::new(stderr.into(), status);
-                    }
-                }
-                Err(e) => {
-                    return Status::new(
-                        format!("Failed to execute nmcli: {}", e),
-                        ExitStatus::default(),
-                    );
-                }
-            }
-        }
+    match list.iter().find(|network| network.in_use) {
+        Some(network) => disconnect_status(run(Command::new("nmcli").args([
+            "connection",
+            "down",
+            &network.ssid,
+        ]))),
+        // nothing was disconnected, which is not a success
+        None => Status::new(
+            "No connected network found".to_string(),
+            ExitStatus::from_raw(1 << 8),
+        ),
     }
-    Status::new(
-        "No connected network found".to_string(),
-        ExitStatus::default(),
-    )
+}
+
+// nmcli tells what it disconnected on stdout, that is the message shown on success
+fn disconnect_status(output: io::Result<Output>) -> Status {
+    let disconnected = output
+        .as_ref()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    output_status(output, disconnected)
 }
@@ -0,0 +1,228 @@
+use std::process::ExitStatus;
+
+// nmcli exit codes, see EXIT STATUS in nmcli(1)
+const EXIT_TIMEOUT: i32 = 3;
+const EXIT_ACTIVATION_FAILED: i32 = 4;
+const EXIT_NOT_RUNNING: i32 = 8;
+const EXIT_NOT_FOUND: i32 = 10;
+
+// What went wrong with a failed nmcli call, in terms the user can act on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    // a password was typed but the access point rejected it
+    WrongPassword,
+    // the profile has no usable secret and nmcli cannot ask for one
+    SecretsRequired,
+    NetworkNotFound,
+    // associated with the access point but no address was handed out
+    DhcpTimeout,
+    PermissionDenied,
+    Timeout,
+    NotRunning,
+    // connected, but the network holds the traffic back until the user signs in to its portal
+    CaptivePortal,
+    // anything else, only the message of nmcli is shown
+    Other,
+}
+
+impl FailureKind {
+    // Classify a failed call from its exit status and the message nmcli printed
+    // The activation failure reasons are matched first since they all share exit code 4
+    pub fn classify(status: ExitStatus, message: &str) -> Self {
+        let message = message.to_lowercase();
+        if message.contains("secrets were required") || message.contains("no secrets") {
+            FailureKind::SecretsRequired
+        } else if message.contains("ip configuration could not be reserved")
+            || message.contains("dhcp")
+        {
+            FailureKind::DhcpTimeout
+        } else if message.contains("not authorized")
+            || message.contains("insufficient privileges")
+            || message.contains("permission denied")
+        {
+            FailureKind::PermissionDenied
+        } else if message.contains("no network with ssid") || message.contains("could not be found")
+        {
+            FailureKind::NetworkNotFound
+        } else if message.contains("networkmanager is not running") {
+            FailureKind::NotRunning
+        } else {
+            match status.code() {
+                Some(EXIT_TIMEOUT) => FailureKind::Timeout,
+                Some(EXIT_NOT_RUNNING) => FailureKind::NotRunning,
+                Some(EXIT_NOT_FOUND) => FailureKind::NetworkNotFound,
+                Some(EXIT_ACTIVATION_FAILED) if message.contains("timeout") => FailureKind::Timeout,
+                _ if message.contains("timed out") => FailureKind::Timeout,
+                _ => FailureKind::Other,
+            }
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            FailureKind::WrongPassword => "Wrong password",
+            FailureKind::SecretsRequired => "Password required",
+            FailureKind::NetworkNotFound => "Network not found",
+            FailureKind::DhcpTimeout => "No IP address",
+            FailureKind::PermissionDenied => "Permission denied",
+            FailureKind::Timeout => "Timed out",
+            FailureKind::NotRunning => "NetworkManager is not running",
+            FailureKind::CaptivePortal => "Sign-in required",
+            FailureKind::Other => "Error",
+        }
+    }
+
+    pub fn explanation(&self) -> Option<&'static str> {
+        match self {
+            FailureKind::WrongPassword => {
+                Some("The access point rejected the password that was entered.")
+            }
+            FailureKind::SecretsRequired => {
+                Some("The saved profile has no valid password for this network.")
+            }
+            FailureKind::NetworkNotFound => {
+                Some("The network is out of range or was not seen by the last scan.")
+            }
+            FailureKind::DhcpTimeout => {
+                Some("The network accepted the connection but did not hand out an IP address.")
+            }
+            FailureKind::PermissionDenied => {
+                Some("Your user is not allowed to change this connection.")
+            }
+            FailureKind::Timeout => Some("NetworkManager gave up waiting for the connection."),
+            FailureKind::NotRunning => Some("nmcli cannot reach the NetworkManager daemon."),
+            FailureKind::CaptivePortal => {
+                Some("The network blocks the internet until you sign in.")
+            }
+            FailureKind::Other => None,
+        }
+    }
+
+    pub fn suggestion(&self) -> Option<&'static str> {
+        match self {
+            FailureKind::WrongPassword | FailureKind::SecretsRequired => {
+                Some("Press (r) to re-enter the password.")
+            }
+            FailureKind::NetworkNotFound => {
+                Some("Move closer or rescan with Ctrl+R and try again.")
+            }
+            FailureKind::DhcpTimeout => Some("Try again, or check the DHCP server of the network."),
+            FailureKind::PermissionDenied => {
+                Some("Run nmtui as a user allowed by polkit, or with sudo.")
+            }
+            FailureKind::Timeout => Some("Check the signal strength and try again."),
+            FailureKind::NotRunning => Some("Start it with `systemctl start NetworkManager`."),
+            FailureKind::CaptivePortal => Some("Sign in to its portal from a browser."),
+            FailureKind::Other => None,
+        }
+    }
+
+    // the password prompt can be opened again right from the popup
+    pub fn offers_password_retry(&self) -> bool {
+        matches!(
+            self,
+            FailureKind::WrongPassword | FailureKind::SecretsRequired
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    fn classify(code: i32, message: &str) -> FailureKind {
+        FailureKind::classify(ExitStatus::from_raw(code << 8), message)
+    }
+
+    #[test]
+    fn classifies_by_message() {
+        let cases = [
+            (
+                "Error: Connection activation failed: Secrets were required, but not provided.",
+                FailureKind::SecretsRequired,
+            ),
+            (
+                "Error: Connection activation failed: No secrets provided.",
+                FailureKind::SecretsRequired,
+            ),
+            (
+                "Error: Connection activation failed: IP configuration could not be reserved (no available address, timeout, etc.).",
+                FailureKind::DhcpTimeout,
+            ),
+            (
+                "Error: Connection activation failed: DHCP client failed to start.",
+                FailureKind::DhcpTimeout,
+            ),
+            (
+                "Error: Connection activation failed: Not authorized to control networking.",
+                FailureKind::PermissionDenied,
+            ),
+            (
+                "Error: Failed to add/activate new connection: Insufficient privileges.",
+                FailureKind::PermissionDenied,
+            ),
+            ("Error: Permission denied.", FailureKind::PermissionDenied),
+            (
+                "Error: No network with SSID 'Cafe' found.",
+                FailureKind::NetworkNotFound,
+            ),
+            (
+                "Error: Connection 'Cafe' could not be found.",
+                FailureKind::NetworkNotFound,
+            ),
+            (
+                "Error: NetworkManager is not running.",
+                FailureKind::NotRunning,
+            ),
+            (
+                "Error: Connection activation failed: The device could not be readied.",
+                FailureKind::Other,
+            ),
+        ];
+        for (message, kind) in cases {
+            // exit code 4 is shared by all activation failures, the message decides
+            assert_eq!(classify(4, message), kind, "{}", message);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_exit_code() {
+        let cases = [
+            (EXIT_TIMEOUT, "Error: some reply", FailureKind::Timeout),
+            (
+                EXIT_NOT_RUNNING,
+                "Error: some reply",
+                FailureKind::NotRunning,
+            ),
+            (
+                EXIT_NOT_FOUND,
+                "Error: some reply",
+                FailureKind::NetworkNotFound,
+            ),
+            (
+                EXIT_ACTIVATION_FAILED,
+                "Error: Timeout expired",
+                FailureKind::Timeout,
+            ),
+            (
+                EXIT_ACTIVATION_FAILED,
+                "Error: some reply",
+                FailureKind::Other,
+            ),
+            (1, "Error: the operation timed out", FailureKind::Timeout),
+            (1, "Error: some reply", FailureKind::Other),
+        ];
+        for (code, message, kind) in cases {
+            assert_eq!(classify(code, message), kind, "{} {}", code, message);
+        }
+    }
+
+    #[test]
+    fn only_secret_failures_offer_a_retry() {
+        assert!(FailureKind::WrongPassword.offers_password_retry());
+        assert!(FailureKind::SecretsRequired.offers_password_retry());
+        assert!(!FailureKind::Timeout.offers_password_retry());
+        assert_eq!(FailureKind::Other.suggestion(), None);
+    }
+}
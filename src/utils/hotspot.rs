@@ -0,0 +1,164 @@
+use crate::apps::handlers::status::Status;
+use crate::utils::command::{run, run_status};
+use crate::utils::saved_connection::wifi_profiles;
+use crate::utils::terse::get_values;
+use qrcode::QrCode;
+use qrcode::render::unicode::Dense1x2;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, ExitStatus};
+
+// name of the profile nmcli creates for the hotspot. It only names new profiles, a hotspot is told
+// apart from the ordinary saved connections by its access point mode (802-11-wireless.mode ap)
+pub const HOTSPOT_CONNECTION_NAME: &str = "Hotspot";
+
+const PASSWORD_CHARSET: &[u8] = b"abcdefghijkmnpqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Band {
+    #[default]
+    Bg,
+    A,
+}
+
+impl Band {
+    // value expected by the band argument of nmcli
+    pub fn as_nmcli_arg(&self) -> &'static str {
+        match self {
+            Band::Bg => "bg",
+            Band::A => "a",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Band::Bg => "2.4 GHz",
+            Band::A => "5 GHz",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            Band::Bg => Band::A,
+            Band::A => Band::Bg,
+        }
+    }
+}
+
+// Create and activate an access point
+// nmcli device wifi hotspot con-name Hotspot ssid <SSID> band <bg|a> password <PASSWORD> [ifname <DEVICE>]
+pub fn start_hotspot(ssid: &str, band: Band, password: &str, ifname: Option<&str>) -> Status {
+    let mut command = Command::new("nmcli");
+    command.args([
+        "device",
+        "wifi",
+        "hotspot",
+        "con-name",
+        HOTSPOT_CONNECTION_NAME,
+        "ssid",
+        ssid,
+        "band",
+        band.as_nmcli_arg(),
+        "password",
+        password,
+    ]);
+    if let Some(ifname) = ifname {
+        command.args(["ifname", ifname]);
+    }
+    run_status(&mut command, format!("Hotspot '{}' is up", ssid))
+}
+
+// Uuid of the access point profile that is up, by its 802-11-wireless.mode since the profile may
+// have been renamed or created by another tool
+fn active_hotspot() -> Option<String> {
+    wifi_profiles(true)
+        .ok()?
+        .into_iter()
+        .find(|profile| profile.settings.mode == "ap")
+        .map(|profile| profile.uuid)
+}
+
+// nmcli connection down uuid <UUID>
+pub fn stop_hotspot() -> Status {
+    match active_hotspot() {
+        Some(uuid) => run_status(
+            Command::new("nmcli").args(["connection", "down", "uuid", &uuid]),
+            "Hotspot stopped".to_string(),
+        ),
+        None => Status::new(
+            "No hotspot is running".to_string(),
+            ExitStatus::from_raw(1 << 8),
+        ),
+    }
+}
+
+pub fn is_hotspot_running() -> bool {
+    active_hotspot().is_some()
+}
+
+// Read the ssid and password of the running hotspot so that a hotspot started before nmtui was
+// opened can still be shown with the right QR code
+// nmcli -s -g <FIELD> connection show uuid <UUID>
+pub fn hotspot_details() -> Option<(String, String)> {
+    let uuid = active_hotspot()?;
+    let field = |name: &str| -> Option<String> {
+        let output = run(Command::new("nmcli").args([
+            "-s",
+            "-g",
+            name,
+            "connection",
+            "show",
+            "uuid",
+            &uuid,
+        ]))
+        .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        get_values(&String::from_utf8_lossy(&output.stdout))
+            .into_iter()
+            .next()
+    };
+    Some((
+        field("802-11-wireless.ssid")?,
+        field("802-11-wireless-security.psk")?,
+    ))
+}
+
+// Generate a random WPA password, leaving out the characters that are easy to misread
+pub fn generate_password(len: usize) -> String {
+    // RandomState is seeded from the os so this is good enough without pulling in a rng crate
+    let state = RandomState::new();
+    (0..len)
+        .map(|i| {
+            let mut hasher = state.build_hasher();
+            hasher.write_usize(i);
+            let index = hasher.finish() as usize % PASSWORD_CHARSET.len();
+            PASSWORD_CHARSET[index] as char
+        })
+        .collect()
+}
+
+// Render the QR code that phones read to join the network, as rows of unicode half blocks
+pub fn wifi_qr_code(ssid: &str, password: &str) -> Option<String> {
+    // special characters of the WIFI: scheme have to be escaped with a backslash
+    let escape = |value: &str| {
+        value.chars().fold(String::new(), |mut escaped, c| {
+            if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+            escaped
+        })
+    };
+    let payload = format!("WIFI:T:WPA;S:{};P:{};;", escape(ssid), escape(password));
+    let code = QrCode::new(payload.as_bytes()).ok()?;
+    Some(
+        code.render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .quiet_zone(true)
+            .build(),
+    )
+}
@@ -0,0 +1,30 @@
+use crate::error::Result;
+use crate::utils::command::run_checked;
+use crate::utils::terse::records;
+use std::process::Command;
+
+#[derive(Debug, Clone)]
+pub struct WifiInterface {
+    pub name: String,
+    // device state as reported by nmcli (connected, disconnected, unavailable, ...)
+    pub state: String,
+}
+
+// List the wireless devices known to NetworkManager
+// nmcli -t -f DEVICE,TYPE,STATE device status
+pub fn wifi_interfaces() -> Result<Vec<WifiInterface>> {
+    let output = run_checked(Command::new("nmcli").args([
+        "-t",
+        "-f",
+        "DEVICE,TYPE,STATE",
+        "device",
+        "status",
+    ]))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(records::<3>(&stdout)?
+        .into_iter()
+        .filter(|[name, device_type, _]| !name.is_empty() && device_type == "wifi")
+        .map(|[name, _, state]| WifiInterface { name, state })
+        .collect())
+}
@@ -0,0 +1,120 @@
+use std::env;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// set once from the command line, nothing is logged when it was never initialized
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(level: &str) -> Result<Self, Self::Err> {
+        match level.to_ascii_lowercase().as_str() {
+            "error" => Ok(LogLevel::Error),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err(format!(
+                "unknown log level '{}', expected one of error, warn, info, debug",
+                level
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+        };
+        write!(f, "{}", level)
+    }
+}
+
+#[derive(Debug)]
+struct Logger {
+    level: LogLevel,
+    file: Mutex<File>,
+}
+
+// $XDG_STATE_HOME/nmtui/nmtui.log, falling back to ~/.local/state and then the working directory
+pub fn default_log_path() -> PathBuf {
+    let state_dir = env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+        .unwrap_or_default();
+    state_dir.join("nmtui").join("nmtui.log")
+}
+
+// Start appending log records of `level` and above to the file at `path`
+pub fn init(path: &Path, level: LogLevel) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let _ = LOGGER.set(Logger {
+        level,
+        file: Mutex::new(file),
+    });
+    Ok(())
+}
+
+pub fn enabled(level: LogLevel) -> bool {
+    LOGGER.get().is_some_and(|logger| level <= logger.level)
+}
+
+// Write one record as "<unix time> <LEVEL> <message>"
+pub fn log(level: LogLevel, message: &str) {
+    let Some(logger) = LOGGER.get() else {
+        return;
+    };
+    if level > logger.level {
+        return;
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    if let Ok(mut file) = logger.file.lock() {
+        let _ = writeln!(
+            file,
+            "{}.{:03} {:<5} {}",
+            timestamp.as_secs(),
+            timestamp.subsec_millis(),
+            level,
+            message
+        );
+    }
+}
+
+pub fn error(message: &str) {
+    log(LogLevel::Error, message);
+}
+
+pub fn warn(message: &str) {
+    log(LogLevel::Warn, message);
+}
+
+pub fn info(message: &str) {
+    log(LogLevel::Info, message);
+}
+
+pub fn debug(message: &str) {
+    log(LogLevel::Debug, message);
+}
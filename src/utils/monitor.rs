@@ -0,0 +1,109 @@
+use crate::utils::command::untranslated;
+use crate::utils::logger;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorEvent {
+    // "wlan0: connected", "wlan0: disconnected", ...
+    DeviceState { device: String, state: String },
+    // "'Home' is now the primary connection", None for "There's no primary connection"
+    PrimaryConnection(Option<String>),
+    // any other line, which still means something changed
+    Other(String),
+}
+
+impl MonitorEvent {
+    fn parse(line: &str) -> Self {
+        if let Some(name) = line
+            .strip_prefix('\'')
+            .and_then(|rest| rest.strip_suffix("' is now the primary connection"))
+        {
+            return MonitorEvent::PrimaryConnection(Some(name.to_string()));
+        }
+        if line == "There's no primary connection" {
+            return MonitorEvent::PrimaryConnection(None);
+        }
+        if let Some((device, state)) = line.split_once(": ")
+            && !device.contains(' ')
+        {
+            return MonitorEvent::DeviceState {
+                device: device.to_string(),
+                state: state.to_string(),
+            };
+        }
+        MonitorEvent::Other(line.to_string())
+    }
+
+    // Short message worth interrupting the user with, None for the intermediate steps of an
+    // activation (prepare, config, ip-config, ...) that only add noise
+    pub fn notification(&self) -> Option<String> {
+        match self {
+            MonitorEvent::DeviceState { device, state }
+                if state == "connected" || state == "disconnected" || state == "unavailable" =>
+            {
+                Some(format!("{} is now {}", device, state))
+            }
+            MonitorEvent::PrimaryConnection(Some(name)) => {
+                Some(format!("'{}' is now the primary connection", name))
+            }
+            MonitorEvent::PrimaryConnection(None) => Some("No primary connection".to_string()),
+            _ => None,
+        }
+    }
+}
+
+// A running `nmcli monitor`, the process is stopped when this is dropped
+#[derive(Debug)]
+pub struct NmcliMonitor {
+    child: Child,
+    pub events: Receiver<MonitorEvent>,
+}
+
+impl Drop for NmcliMonitor {
+    // the reader thread is blocked until nmcli prints something, so nmcli would otherwise stay
+    // around after nmtui quit until the next state change
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        logger::info("`nmcli monitor` stopped");
+    }
+}
+
+// Follow NetworkManager state changes in the background
+// nmcli monitor
+//
+// Every line nmcli prints is sent as an event, the thread ends once nmcli is stopped. None is
+// returned when nmcli could not be started.
+pub fn spawn_monitor() -> Option<NmcliMonitor> {
+    let mut child = match untranslated(&mut Command::new("nmcli"))
+        .arg("monitor")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            logger::error(&format!("`nmcli monitor` could not be started: {}", e));
+            return None;
+        }
+    };
+    logger::info("`nmcli monitor` started");
+    let stdout = child.stdout.take()?;
+
+    let (sender, events) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            logger::debug(&format!("nmcli monitor: {}", line.trim()));
+            if sender.send(MonitorEvent::parse(line.trim())).is_err() {
+                break;
+            }
+        }
+    });
+    Some(NmcliMonitor { child, events })
+}
@@ -0,0 +1,94 @@
+use crate::utils::command::run;
+use crate::utils::connection_info::{Connectivity, check_connectivity};
+use crate::utils::logger;
+use std::io;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+// A connection that NetworkManager found to be held back by a captive portal
+#[derive(Debug)]
+pub struct PortalDetected {
+    // the result of the connection, reported again with the portal
+    pub message: String,
+    // page that leads to the sign-in page, None when none is configured
+    pub url: Option<String>,
+}
+
+// Probe the internet in the background after connecting, it can take a few seconds
+//
+// Only a portal is sent back, the connection was already reported as a success. The url is the
+// --portal-url one, or else the uri NetworkManager checks the connectivity with: captive portals
+// redirect unencrypted requests like that one to their sign-in page.
+pub fn detect_portal(message: String, url: Option<String>, portals: Sender<PortalDetected>) {
+    thread::spawn(move || {
+        if check_connectivity() == Connectivity::Portal {
+            let url = url.or_else(connectivity_check_uri);
+            let _ = portals.send(PortalDetected { message, url });
+        }
+    });
+}
+
+// busctl get-property org.freedesktop.NetworkManager /org/freedesktop/NetworkManager
+//     org.freedesktop.NetworkManager ConnectivityCheckUri
+fn connectivity_check_uri() -> Option<String> {
+    let output = run(Command::new("busctl").args([
+        "get-property",
+        "org.freedesktop.NetworkManager",
+        "/org/freedesktop/NetworkManager",
+        "org.freedesktop.NetworkManager",
+        "ConnectivityCheckUri",
+    ]))
+    .ok()
+    .filter(|output| output.status.success())?;
+    parse_string_property(&String::from_utf8_lossy(&output.stdout))
+}
+
+// busctl prints a string property as `s "VALUE"`, empty when the check is turned off
+fn parse_string_property(output: &str) -> Option<String> {
+    output
+        .trim()
+        .strip_prefix("s \"")?
+        .strip_suffix('"')
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+// Open the sign-in page of a captive portal with the --portal-browser command
+//
+// The command is split on whitespace and the url is added as its last argument. The browser is
+// left running, a thread waits for it so that it does not stay around as a zombie.
+pub fn open_portal(browser: &str, url: &str) -> io::Result<()> {
+    let mut words = browser.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the command is empty"))?;
+
+    let mut child = Command::new(program)
+        .args(words)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    logger::info(&format!("opened {} with `{}`", url, browser));
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_connectivity_check_uri() {
+        assert_eq!(
+            parse_string_property("s \"http://nmcheck.gnome.org/check_network_status.txt\"\n")
+                .as_deref(),
+            Some("http://nmcheck.gnome.org/check_network_status.txt")
+        );
+        // the connectivity check is turned off
+        assert_eq!(parse_string_property("s \"\"\n"), None);
+        assert_eq!(parse_string_property(""), None);
+    }
+}
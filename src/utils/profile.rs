@@ -0,0 +1,48 @@
+use crate::apps::handlers::status::Status;
+use crate::utils::command::run_status;
+use std::process::Command;
+
+// Give a saved profile another name, its settings and uuid stay the same
+// nmcli connection modify uuid <UUID> connection.id <NAME>
+pub fn rename_profile(uuid: &str, name: &str, new_name: &str) -> Status {
+    run_status(
+        Command::new("nmcli").args([
+            "connection",
+            "modify",
+            "uuid",
+            uuid,
+            "connection.id",
+            new_name,
+        ]),
+        format!("Renamed '{}' to '{}'", name, new_name),
+    )
+}
+
+// Copy a saved profile with every setting under a new name and uuid, to keep variants of it
+// nmcli connection clone uuid <UUID> <NAME>
+pub fn clone_profile(uuid: &str, name: &str, new_name: &str) -> Status {
+    run_status(
+        Command::new("nmcli").args(["connection", "clone", "uuid", uuid, new_name]),
+        format!("Cloned '{}' as '{}'", name, new_name),
+    )
+}
+
+// First of "NAME 2", "NAME 3", ... that no profile uses yet, offered as the name of a clone
+pub fn free_name<'a>(name: &str, taken: impl Iterator<Item = &'a str> + Clone) -> String {
+    (2..)
+        .map(|n| format!("{} {}", name, n))
+        .find(|candidate| !taken.clone().any(|name| name == candidate))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_get_the_first_free_number() {
+        let taken = ["Guest", "Guest 2", "Guest 4"];
+        assert_eq!(free_name("Guest", taken.iter().copied()), "Guest 3");
+        assert_eq!(free_name("Office", taken.iter().copied()), "Office 2");
+    }
+}
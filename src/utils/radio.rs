@@ -0,0 +1,43 @@
+use crate::apps::handlers::status::Status;
+use crate::utils::command::{run, run_status};
+use std::process::Command;
+
+// nmcli radio wifi
+pub fn wifi_radio_enabled() -> bool {
+    nmcli_reports_enabled(&["radio", "wifi"])
+}
+
+// nmcli networking
+pub fn networking_enabled() -> bool {
+    nmcli_reports_enabled(&["networking"])
+}
+
+// nmcli radio wifi <on|off>
+pub fn set_wifi_radio(enabled: bool) -> Status {
+    let state = if enabled { "on" } else { "off" };
+    switch(
+        &["radio", "wifi", state],
+        format!("Wi-Fi radio turned {}", state),
+    )
+}
+
+// nmcli networking <on|off>
+pub fn set_networking(enabled: bool) -> Status {
+    let state = if enabled { "on" } else { "off" };
+    switch(
+        &["networking", state],
+        format!("Networking turned {}", state),
+    )
+}
+
+// both commands print "enabled" or "disabled", anything else (nmcli missing, NM not running)
+// is treated as disabled
+fn nmcli_reports_enabled(args: &[&str]) -> bool {
+    run(Command::new("nmcli").args(args))
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "enabled")
+        .unwrap_or(false)
+}
+
+fn switch(args: &[&str], success_message: String) -> Status {
+    run_status(Command::new("nmcli").args(args), success_message)
+}
@@ -1,28 +1,161 @@
+use crate::error::Result;
+use crate::utils::command::run_checked;
+use crate::utils::terse::{records, split_fields};
+use std::collections::HashMap;
 use std::process::Command;
 
-pub fn saved_connections() -> Vec<String> {
-    // nmcli -t -f NAME,TYPE connection show
-    let output = Command::new("nmcli")
-        .args(["-t", "-f", "NAME,TYPE", "connection", "show"])
-        .output()
-        .expect(" Failed to execute nmcli command");
+// Settings of a wireless profile that `nmcli connection show` without an id does not list
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WirelessSettings {
+    // network the profile joins, the profile name can be anything
+    pub ssid: String,
+    // "infrastructure" for a client, "ap" for an access point like the one of the hotspot view
+    pub mode: String,
+}
+
+// A saved wifi profile with the network it joins, the name alone says nothing about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct WifiProfile {
+    pub name: String,
+    pub uuid: String,
+    pub settings: WirelessSettings,
+}
 
-    let mut ssids: Vec<String> = Vec::new();
-    if !output.status.success() {
-        return ssids;
+// Every saved wifi profile, or only the active ones, with its ssid and mode
+// nmcli -t -f NAME,UUID,TYPE connection show [--active]
+// and the wireless settings of the listed profiles
+pub fn wifi_profiles(active: bool) -> Result<Vec<WifiProfile>> {
+    let mut command = Command::new("nmcli");
+    command.args(["-t", "-f", "NAME,UUID,TYPE", "connection", "show"]);
+    if active {
+        command.arg("--active");
     }
+    let output = run_checked(&mut command)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let profiles: Vec<[String; 3]> = records::<3>(&stdout)?
+        .into_iter()
+        .filter(|[name, _, connection_type]| {
+            !name.is_empty() && connection_type == "802-11-wireless"
+        })
+        .collect();
+    let uuids: Vec<String> = profiles.iter().map(|[_, uuid, _]| uuid.clone()).collect();
+    let mut settings = wireless_settings(&uuids)?;
+    Ok(profiles
+        .into_iter()
+        .map(|[name, uuid, _]| WifiProfile {
+            settings: settings.remove(&uuid).unwrap_or_default(),
+            name,
+            uuid,
+        })
+        .collect())
+}
+
+// Ssids of the networks a saved client profile joins, whatever the profiles are named
+pub fn saved_connections() -> Result<Vec<String>> {
+    Ok(wifi_profiles(false)?
+        .into_iter()
+        .filter(|profile| profile.settings.mode != "ap")
+        .map(|profile| profile.settings.ssid)
+        .collect())
+}
+
+// Names of every saved profile, whatever its type (wifi, ethernet, vpn, ...)
+// nmcli -t -f NAME connection show
+pub fn profile_names() -> Result<Vec<String>> {
+    let output =
+        run_checked(Command::new("nmcli").args(["-t", "-f", "NAME", "connection", "show"]))?;
     let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(records::<1>(&stdout)?
+        .into_iter()
+        .map(|[name]| name)
+        .collect())
+}
 
-    // header is already handled by nmcli with -t flag
-    for line in stdout.lines() {
-        let mut parts = line.splitn(2, ':');
-        let ssid = parts.next().unwrap_or("").to_string();
-        let connection_type = parts.next().unwrap_or("").to_string();
+// Name and uuid of the client profiles that join the network, several profiles can
+pub fn network_profiles(ssid: &str) -> Result<Vec<(String, String)>> {
+    Ok(wifi_profiles(false)?
+        .into_iter()
+        .filter(|profile| profile.settings.mode != "ap" && profile.settings.ssid == ssid)
+        .map(|profile| (profile.name, profile.uuid))
+        .collect())
+}
+
+// Wireless settings of several profiles with a single nmcli call, by uuid
+// nmcli -t -f connection.uuid,802-11-wireless.ssid,802-11-wireless.mode connection show
+//     uuid <UUID> uuid <UUID> ...
+pub fn wireless_settings(uuids: &[String]) -> Result<HashMap<String, WirelessSettings>> {
+    if uuids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut command = Command::new("nmcli");
+    command.args([
+        "-t",
+        "-f",
+        "connection.uuid,802-11-wireless.ssid,802-11-wireless.mode",
+        "connection",
+        "show",
+    ]);
+    for uuid in uuids {
+        command.args(["uuid", uuid]);
+    }
+    let output = run_checked(&mut command)?;
+    Ok(parse_wireless_settings(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
 
-        // we are only interested in wifi saved connections
-        if !ssid.is_empty() && !connection_type.is_empty() && connection_type == "802-11-wireless" {
-            ssids.push(ssid);
+// Every profile is printed as `setting.property:value` lines starting with its connection.uuid
+fn parse_wireless_settings(output: &str) -> HashMap<String, WirelessSettings> {
+    let mut settings: HashMap<String, WirelessSettings> = HashMap::new();
+    let mut uuid = None;
+    for line in output.lines().filter(|line| !line.is_empty()) {
+        let [property, value] = split_fields(line).try_into().unwrap_or_default();
+        if property == "connection.uuid" {
+            settings.entry(value.clone()).or_default();
+            uuid = Some(value);
+            continue;
         }
+        let Some(profile) = uuid.as_ref().and_then(|uuid| settings.get_mut(uuid)) else {
+            continue;
+        };
+        match property.as_str() {
+            "802-11-wireless.ssid" => profile.ssid = value,
+            "802-11-wireless.mode" => profile.mode = value,
+            _ => {}
+        }
+    }
+    settings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_settings_of_every_profile() {
+        let output = "connection.uuid:5d1c7f36-6a39-4c5b-9c1f-7a4f0e6a1111\n\
+            802-11-wireless.ssid:Coffee\\:Shop\n\
+            802-11-wireless.mode:infrastructure\n\
+            \n\
+            connection.uuid:0b7c9a53-3c1e-4d8e-9a39-2f6a4f0e6a11\n\
+            802-11-wireless.ssid:nmtui-hotspot\n\
+            802-11-wireless.mode:ap\n";
+        let settings = parse_wireless_settings(output);
+        assert_eq!(settings.len(), 2);
+        assert_eq!(
+            settings["5d1c7f36-6a39-4c5b-9c1f-7a4f0e6a1111"],
+            WirelessSettings {
+                ssid: "Coffee:Shop".to_string(),
+                mode: "infrastructure".to_string(),
+            }
+        );
+        assert_eq!(
+            settings["0b7c9a53-3c1e-4d8e-9a39-2f6a4f0e6a11"],
+            WirelessSettings {
+                ssid: "nmtui-hotspot".to_string(),
+                mode: "ap".to_string(),
+            }
+        );
     }
-    ssids
 }
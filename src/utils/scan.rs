@@ -1,64 +1,139 @@
 use crate::WifiNetwork;
+use crate::error::{Error, Result};
+use crate::utils::command::run_checked;
+use crate::utils::logger;
 use crate::utils::saved_connection::saved_connections;
+use crate::utils::signal_history::SignalHistory;
+use crate::utils::terse::records;
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, PoisonError, RwLock};
 use std::thread;
 
-pub fn scan_networks(wifi_list: Arc<RwLock<Vec<WifiNetwork>>>, is_scanning: Arc<AtomicBool>) {
+pub fn scan_networks(
+    wifi_list: Arc<RwLock<Vec<WifiNetwork>>>,
+    is_scanning: Arc<AtomicBool>,
+    signal_history: Arc<RwLock<SignalHistory>>,
+    ifname: Option<String>,
+    errors: Sender<Error>,
+) {
     is_scanning.store(true, Ordering::SeqCst);
-    // // nmcli -t -f IN-USE,SSID,SECURITY device wifi list
     thread::spawn(move || {
-        let output = Command::new("nmcli")
-            .args(["-t", "-f", "IN-USE,SSID,SECURITY", "device", "wifi", "list"])
-            .output()
-            .expect("Failed to execute nmcli command");
+        match list_networks(ifname.as_deref(), None) {
+            Ok(networks) => {
+                record_signals(&signal_history, &networks);
+                // the list is replaced as a whole, so it is still usable after a panic elsewhere
+                *wifi_list.write().unwrap_or_else(PoisonError::into_inner) = networks;
+            }
+            Err(e) => {
+                let _ = errors.send(e);
+            }
+        }
+        // cleared even when nmcli failed, otherwise the spinner would never stop
+        is_scanning.store(false, Ordering::SeqCst);
+    });
+}
 
-        if !output.status.success() {
-            return;
+// Update the networks in the background without showing the scanning spinner
+//
+// With `rescan` NetworkManager is asked for a fresh scan and the readings are added to the signal
+// history, this is used for the periodic sampling of the signal panel. Without it only the
+// networks NetworkManager already knows about are re-read, which is enough to follow state changes.
+// Failures are only logged since the user did not ask for these refreshes.
+pub fn refresh_networks(
+    wifi_list: Arc<RwLock<Vec<WifiNetwork>>>,
+    signal_history: Arc<RwLock<SignalHistory>>,
+    ifname: Option<String>,
+    rescan: bool,
+) {
+    thread::spawn(move || {
+        let rescan_arg = if rescan { "yes" } else { "no" };
+        match list_networks(ifname.as_deref(), Some(rescan_arg)) {
+            Ok(networks) => {
+                if rescan {
+                    record_signals(&signal_history, &networks);
+                }
+                *wifi_list.write().unwrap_or_else(PoisonError::into_inner) = networks;
+            }
+            Err(e) => logger::warn(&format!("background refresh failed: {}", e)),
         }
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut networks: Vec<WifiNetwork> = Vec::new();
+    });
+}
 
-        let saved_networks = saved_connections();
+fn record_signals(signal_history: &RwLock<SignalHistory>, networks: &[WifiNetwork]) {
+    let mut history = signal_history
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    for network in networks.iter().filter(|network| !network.bssid.is_empty()) {
+        history.record(&network.bssid, network.signal);
+    }
+}
 
-        for line in stdout.lines() {
-            let mut parts = line.splitn(3, ':');
+// nmcli -t -f IN-USE,BSSID,SIGNAL,CHAN,FREQ,SECURITY,SSID device wifi list [ifname <DEVICE>] [--rescan <yes|no>]
+fn list_networks(ifname: Option<&str>, rescan: Option<&str>) -> Result<Vec<WifiNetwork>> {
+    let mut command = Command::new("nmcli");
+    command.args([
+        "-t",
+        "-f",
+        "IN-USE,BSSID,SIGNAL,CHAN,FREQ,SECURITY,SSID",
+        "device",
+        "wifi",
+        "list",
+    ]);
+    if let Some(ifname) = ifname {
+        command.args(["ifname", ifname]);
+    }
+    if let Some(rescan) = rescan {
+        command.args(["--rescan", rescan]);
+    }
+    let output = run_checked(&mut command)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut networks: Vec<WifiNetwork> = Vec::new();
 
-            let in_use = parts.next() == Some("*");
-            let ssid = parts.next().unwrap_or("").to_string();
+    let saved_networks = saved_connections()?;
 
-            let security = parts
-                .next()
-                .map(|s| {
-                    if s.is_empty() {
-                        "Unsecured".to_string()
-                    } else {
-                        s.to_string()
-                    }
-                })
-                .unwrap_or("?".to_string());
+    for [in_use, bssid, signal, channel, frequency, security, ssid] in records::<7>(&stdout)? {
+        let in_use = in_use == "*";
+        let signal = signal.parse().unwrap_or(0);
+        let channel = channel.parse().unwrap_or(0);
+        // reported as "2412 MHz"
+        let frequency = frequency
+            .split_whitespace()
+            .next()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
 
-            let is_saved = saved_networks.contains(&ssid);
+        let security = if security.is_empty() {
+            "Unsecured".to_string()
+        } else {
+            security
+        };
 
-            if !ssid.is_empty() {
-                networks.push(WifiNetwork {
-                    is_saved,
-                    in_use,
-                    ssid,
-                    security,
-                })
-            }
-        }
-        networks.push(WifiNetwork {
-            is_saved: false,
-            in_use: false,
-            ssid: "Connect to Hidden network".to_string(),
-            security: "?".to_string(),
-        });
+        let is_saved = saved_networks.contains(&ssid);
 
-        let mut wifi_list_lock = wifi_list.write().expect("WifiNetworks lock poisoned");
-        *wifi_list_lock = networks;
-        is_scanning.store(false, Ordering::SeqCst);
+        if !ssid.is_empty() {
+            networks.push(WifiNetwork {
+                is_saved,
+                in_use,
+                ssid,
+                security,
+                bssid,
+                signal,
+                channel,
+                frequency,
+            })
+        }
+    }
+    networks.push(WifiNetwork {
+        is_saved: false,
+        in_use: false,
+        ssid: "Connect to Hidden network".to_string(),
+        security: "?".to_string(),
+        bssid: String::new(),
+        signal: 0,
+        channel: 0,
+        frequency: 0,
     });
+    Ok(networks)
 }
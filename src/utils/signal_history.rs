@@ -0,0 +1,25 @@
+use std::collections::{HashMap, VecDeque};
+
+// number of readings kept per access point, with the periodic sampling of the signal panel this
+// is roughly ten minutes of history
+pub const SIGNAL_HISTORY_LENGTH: usize = 120;
+
+#[derive(Debug, Default)]
+pub struct SignalHistory {
+    // signal readings (0-100) keyed by bssid, oldest first
+    samples: HashMap<String, VecDeque<u8>>,
+}
+
+impl SignalHistory {
+    pub fn record(&mut self, bssid: &str, signal: u8) {
+        let samples = self.samples.entry(bssid.to_string()).or_default();
+        if samples.len() == SIGNAL_HISTORY_LENGTH {
+            samples.pop_front();
+        }
+        samples.push_back(signal);
+    }
+
+    pub fn get(&self, bssid: &str) -> Option<&VecDeque<u8>> {
+        self.samples.get(bssid)
+    }
+}
@@ -0,0 +1,152 @@
+use crate::error::{Error, Result};
+
+// Parsing of the terse output of nmcli (-t and -g)
+//
+// In terse mode fields are separated by ':' and nmcli escapes the ':' and '\' inside of values
+// with a backslash, so "My:Net" is printed as "My\:Net" and a bssid as "AA\:BB\:CC\:DD\:EE\:FF".
+
+// Split one line into its unescaped fields
+pub fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // a trailing backslash has nothing to escape and is kept as is
+            '\\' => field.push(chars.next().unwrap_or('\\')),
+            ':' => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+// Remove the escaping from a single value, as printed by -g with one field
+pub fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unescaped.push(chars.next().unwrap_or('\\'));
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+// Parse every line of the output of `nmcli -t -f <N fields> ...` into a record of N fields
+// Empty lines are skipped and a line with another number of fields is a parse error
+pub fn records<const N: usize>(output: &str) -> Result<Vec<[String; N]>> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            <[String; N]>::try_from(split_fields(line)).map_err(|fields| {
+                Error::Parse(format!(
+                    "expected {} fields, got {} in '{}'",
+                    N,
+                    fields.len(),
+                    line
+                ))
+            })
+        })
+        .collect()
+}
+
+// Values of `nmcli -g <FIELD> ...`, one per line
+pub fn get_values(output: &str) -> Vec<String> {
+    output.lines().map(unescape).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_plain_fields() {
+        assert_eq!(
+            split_fields("Home:802-11-wireless"),
+            ["Home", "802-11-wireless"]
+        );
+    }
+
+    #[test]
+    fn keeps_empty_fields() {
+        assert_eq!(split_fields("*::"), ["*", "", ""]);
+        assert_eq!(split_fields(""), [""]);
+    }
+
+    #[test]
+    fn unescapes_colons_in_bssid() {
+        assert_eq!(
+            split_fields(r"*:AA\:BB\:CC\:DD\:EE\:FF:70"),
+            ["*", "AA:BB:CC:DD:EE:FF", "70"]
+        );
+    }
+
+    #[test]
+    fn ssid_with_colon() {
+        assert_eq!(
+            split_fields(r"My\:Network:802-11-wireless"),
+            ["My:Network", "802-11-wireless"]
+        );
+    }
+
+    #[test]
+    fn ssid_with_backslash() {
+        assert_eq!(split_fields(r"back\\slash:wifi"), [r"back\slash", "wifi"]);
+    }
+
+    #[test]
+    fn ssid_ending_with_backslash() {
+        // the escaped backslash must not escape the separator after it
+        assert_eq!(split_fields(r"net\\:wifi"), [r"net\", "wifi"]);
+    }
+
+    #[test]
+    fn ssid_made_of_separators() {
+        assert_eq!(split_fields(r"\:\\\::x"), [r":\:", "x"]);
+    }
+
+    #[test]
+    fn trailing_backslash_is_kept() {
+        assert_eq!(split_fields(r"odd\"), [r"odd\"]);
+        assert_eq!(unescape(r"odd\"), r"odd\");
+    }
+
+    #[test]
+    fn unicode_ssid() {
+        assert_eq!(split_fields(r"Café☕\:5G:wifi"), ["Café☕:5G", "wifi"]);
+    }
+
+    #[test]
+    fn parses_records() {
+        let output = "Home:802-11-wireless:1700000000\n\nWork\\:Guest:802-11-wireless:0\n";
+        let records = records::<3>(output).unwrap();
+        assert_eq!(
+            records,
+            [
+                ["Home", "802-11-wireless", "1700000000"].map(String::from),
+                ["Work:Guest", "802-11-wireless", "0"].map(String::from),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_records_with_wrong_field_count() {
+        let error = records::<3>("Home:802-11-wireless\n").unwrap_err();
+        assert!(matches!(error, Error::Parse(message) if message.contains("expected 3 fields")));
+        // an unescaped colon would shift the fields, which must not go unnoticed
+        assert!(records::<2>("My:Net:wifi").is_err());
+    }
+
+    #[test]
+    fn unescapes_get_values() {
+        assert_eq!(
+            get_values("My\\:Net\nsecret\\\\pass\n"),
+            ["My:Net", r"secret\pass"]
+        );
+    }
+}
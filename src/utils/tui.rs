@@ -1,7 +1,23 @@
 use crate::apps::core::App;
-pub fn tui() -> Result<(), Box<dyn std::error::Error>> {
+use crate::utils::cli::Args;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use std::{io, panic};
+pub fn tui(args: Args) -> Result<(), Box<dyn std::error::Error>> {
     let mut terminal = ratatui::init();
-    let app_result = App::default().run(&mut terminal);
+    // the panic hook of ratatui restores the terminal but leaves the mouse captured, so the
+    // capture is released first, before the shell gets the mouse events again
+    let restore_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+        restore_hook(info);
+    }));
+    // the mouse is captured for the clicks on the lists and popup buttons
+    let app_result = match execute!(io::stdout(), EnableMouseCapture) {
+        Ok(()) => App::new(args).run(&mut terminal),
+        Err(e) => Err(e.into()),
+    };
+    let _ = execute!(io::stdout(), DisableMouseCapture);
     ratatui::try_restore().expect("Failed to restore terminal");
     app_result
 }
@@ -0,0 +1,186 @@
+use crate::apps::handlers::status::Status;
+use crate::utils::command::{output_status, run, run_status, run_with_input};
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::{env, fs};
+
+// Bring up a vpn or wireguard connection by its uuid
+// nmcli connection up uuid <UUID> [passwd-file /dev/stdin]
+pub fn activate_vpn(uuid: &str, secret: Option<(&str, &str)>) -> Status {
+    let mut command = Command::new("nmcli");
+    command.args(["connection", "up", "uuid", uuid]);
+
+    // the secret is piped through stdin so that it never shows up in the process list
+    let output = match secret {
+        Some((key, value)) => run_with_input(
+            command.args(["passwd-file", "/dev/stdin"]),
+            &format!("{}:{}\n", key, value),
+        ),
+        None => run(&mut command),
+    };
+    output_status(output, "VPN connection activated".to_string())
+}
+
+// Bring down an active vpn or wireguard connection by its uuid
+pub fn deactivate_vpn(uuid: &str) -> Status {
+    run_status(
+        Command::new("nmcli").args(["connection", "down", "uuid", uuid]),
+        "VPN connection deactivated".to_string(),
+    )
+}
+
+// nmcli reports this when the activation is blocked waiting for a password or key
+pub fn needs_secrets(status: &Status) -> bool {
+    !status.status_code.success() && status.status_message.contains("Secrets were required")
+}
+
+// Work out which nmcli vpn plugin handles the file at `path` and check it looks like a config for it
+// Returns the type for `nmcli connection import type <type>` or the reason the file was rejected
+pub fn validate_vpn_config(path: &Path) -> Result<&'static str, String> {
+    let vpn_type = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("conf") => "wireguard",
+        Some("ovpn") => "openvpn",
+        _ => {
+            return Err(format!(
+                "'{}' is neither a WireGuard (.conf) nor an OpenVPN (.ovpn) file",
+                path.display()
+            ));
+        }
+    };
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+
+    // just enough of a sanity check to catch picking the wrong file before nmcli does
+    let looks_valid = if vpn_type == "wireguard" {
+        contents.contains("[Interface]") && contents.contains("PrivateKey")
+    } else {
+        contents
+            .lines()
+            .any(|line| line.trim_start().starts_with("remote "))
+    };
+
+    if looks_valid {
+        Ok(vpn_type)
+    } else if vpn_type == "wireguard" {
+        Err(format!(
+            "'{}' has no [Interface] section with a PrivateKey",
+            path.display()
+        ))
+    } else {
+        Err(format!("'{}' has no remote directive", path.display()))
+    }
+}
+
+// Import a WireGuard or OpenVPN config file as a new connection profile
+// nmcli connection import type <wireguard|openvpn> file <PATH>
+pub fn import_vpn(path: &str) -> Status {
+    let path = expand_home(path.trim());
+
+    let vpn_type = match validate_vpn_config(&path) {
+        Ok(vpn_type) => vpn_type,
+        Err(reason) => {
+            return Status::new(reason, ExitStatus::from_raw(1 << 8));
+        }
+    };
+
+    let output = run(Command::new("nmcli")
+        .arg("connection")
+        .arg("import")
+        .args(["type", vpn_type, "file"])
+        .arg(&path));
+    // nmcli reports the created profile as "Connection 'name' (uuid) successfully added."
+    let imported = output
+        .as_ref()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    output_status(output, imported)
+}
+
+// the path is typed by hand so a leading ~ is expanded the same way a shell would
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a config file in a directory of its own under the temp dir, removed with it
+    fn config_file(name: &str, contents: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("nmtui-vpn-test-{}-{}", std::process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn only_failed_activations_asking_for_secrets_need_them() {
+        let failed = |message: &str| Status::new(message.to_string(), ExitStatus::from_raw(4 << 8));
+        assert!(needs_secrets(&failed(
+            "Error: Connection activation failed: Secrets were required, but not provided."
+        )));
+        assert!(!needs_secrets(&failed(
+            "Error: Connection activation failed: The VPN service stopped unexpectedly."
+        )));
+        assert!(!needs_secrets(&Status::new(
+            "Secrets were required".to_string(),
+            ExitStatus::from_raw(0)
+        )));
+    }
+
+    #[test]
+    fn picks_the_plugin_from_the_extension() {
+        let wireguard = config_file(
+            "wg0.conf",
+            "[Interface]\nPrivateKey = abc=\n[Peer]\nPublicKey = def=\n",
+        );
+        assert_eq!(validate_vpn_config(&wireguard), Ok("wireguard"));
+        let openvpn = config_file("office.ovpn", "client\nremote vpn.example.com 1194\n");
+        assert_eq!(validate_vpn_config(&openvpn), Ok("openvpn"));
+        for path in [wireguard, openvpn] {
+            fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn rejects_files_that_do_not_look_like_configs() {
+        let no_key = config_file("broken.conf", "[Interface]\nAddress = 10.0.0.2/32\n");
+        assert!(
+            validate_vpn_config(&no_key)
+                .unwrap_err()
+                .contains("PrivateKey")
+        );
+        let no_remote = config_file("broken.ovpn", "client\ndev tun\n");
+        assert!(
+            validate_vpn_config(&no_remote)
+                .unwrap_err()
+                .contains("remote")
+        );
+        for path in [no_key, no_remote] {
+            fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        }
+        assert!(
+            validate_vpn_config(Path::new("notes.txt"))
+                .unwrap_err()
+                .contains("neither")
+        );
+    }
+
+    #[test]
+    fn expands_only_a_leading_tilde() {
+        assert_eq!(expand_home("/etc/wg0.conf"), PathBuf::from("/etc/wg0.conf"));
+        assert_eq!(expand_home("a/~/b.conf"), PathBuf::from("a/~/b.conf"));
+        if let Some(home) = env::var_os("HOME") {
+            assert_eq!(
+                expand_home("~/vpn/wg0.conf"),
+                PathBuf::from(home).join("vpn/wg0.conf")
+            );
+        }
+    }
+}